@@ -116,20 +116,23 @@ fn evaluate_interpolating_polys<const N: usize>(
     alpha(u+v)(s) != alpha((u+v)(s))
 */
 #[test]
+#[allow(clippy::match_like_matches_macro)]
 fn test_homomorphic_combinatorial_auction() {
     const N: usize = 2;
     let [alice_poly, bob_poly] = alice_bob_combinatorial_bids();
     let public_key: TropicalAutomorphism<N> = {
-        let a = make_random_monomial().compose(make_random_2terms_triangular());
-        let b = make_random_monomial().compose(make_random_2terms_triangular());
-        a.compose(b).compose(make_random_monomial())
+        let rng = &mut rand::thread_rng();
+        let a = make_random_monomial(rng, 31).compose(make_random_2terms_triangular(rng, 31));
+        let b = make_random_monomial(rng, 31).compose(make_random_2terms_triangular(rng, 31));
+        a.compose(b).compose(make_random_monomial(rng, 31))
     };
 
     let winning_poly = alice_poly.clone() + bob_poly.clone();
-    let alice_commit = public_key.apply(&alice_poly);
-    let bob_commit = public_key.apply(&bob_poly);
+    // README: the public key has no denominators, so the commits are polynomials
+    let alice_commit = public_key.apply(&alice_poly).numerator;
+    let bob_commit = public_key.apply(&bob_poly).numerator;
     let winning_commit = alice_commit.clone() + bob_commit.clone();
-    assert_eq!(public_key.apply(&winning_poly), winning_commit);
+    assert_eq!(public_key.apply(&winning_poly).numerator, winning_commit);
 
     let point_x = [TropicalInt::Integer(1), TropicalInt::Integer(0)];
     let point_y = [TropicalInt::Integer(0), TropicalInt::Integer(1)];
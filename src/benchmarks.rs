@@ -1,4 +1,4 @@
-use rand::Rng as _;
+use rand::{rngs::StdRng, Rng, SeedableRng as _};
 use test::Bencher;
 
 use crate::{
@@ -7,78 +7,111 @@ use crate::{
     tropical_polynomial::{Degree, TropicalPolynomial},
 };
 
+// README: the benchmarks draw their automorphisms from a seeded rng, so every run (and every
+// commit, and both term stores) composes the very same ones. the degrees are kept small,
+// with degrees up to 31 a single 5d composition doesn't fit in memory.
+const BENCH_SEED: u64 = 0x7209;
+const BENCH_MAX_DEGREE: Degree = 1;
+
 pub fn make_random_elementary_triangular_row<const N: usize>(
+    rng: &mut impl Rng,
+    max_degree: Degree,
     i: usize,
 ) -> ([Degree; N], TropicalInt) {
-    let mut rng = rand::thread_rng();
     (
-        core::array::from_fn(|j| if j <= i { 0 } else { rng.gen_range(0..=31) }),
+        core::array::from_fn(|j| {
+            if j <= i {
+                0
+            } else {
+                rng.gen_range(0..=max_degree)
+            }
+        }),
         TropicalInt::from(rng.gen_range(-15..15)),
     )
 }
 
-pub fn make_random_2terms_triangular<const N: usize>() -> TropicalAutomorphism<N> {
+pub fn make_random_2terms_triangular<const N: usize>(
+    rng: &mut impl Rng,
+    max_degree: Degree,
+) -> TropicalAutomorphism<N> {
     (0..N - 1).fold(TropicalAutomorphism::identity(), |acc, variable| {
+        let first = make_random_elementary_triangular_row(rng, max_degree, variable);
+        let second = make_random_elementary_triangular_row(rng, max_degree, variable);
         acc.compose(TropicalAutomorphism::elementary_triangular(
             variable,
-            TropicalPolynomial::from(vec![
-                make_random_elementary_triangular_row(variable),
-                make_random_elementary_triangular_row(variable),
-            ]),
+            TropicalPolynomial::from(vec![first, second]),
         ))
     })
 }
 
 // good chance to have det != 0, it's a benchmark anyways
-pub fn make_random_monomial<const N: usize>() -> TropicalAutomorphism<N> {
-    let mut rng = rand::thread_rng();
-
+pub fn make_random_monomial<const N: usize>(
+    rng: &mut impl Rng,
+    max_degree: Degree,
+) -> TropicalAutomorphism<N> {
     TropicalAutomorphism::monomial(
-        core::array::from_fn(|_| core::array::from_fn(|_| rng.gen_range(0..=31))),
+        core::array::from_fn(|_| core::array::from_fn(|_| rng.gen_range(0..=max_degree))),
         core::array::from_fn(|_| rng.gen_range(-15..15)).map(TropicalInt::from),
     )
 }
 
+fn bench_composition<const N: usize>(
+    bencher: &mut Bencher,
+    [a, b, c]: [TropicalAutomorphism<N>; 3],
+) {
+    bencher.iter(|| a.clone().compose(b.clone()).compose(c.clone()));
+}
+
+fn triangular_monomial_triangular<const N: usize>(
+    rng: &mut impl Rng,
+) -> [TropicalAutomorphism<N>; 3] {
+    let a = make_random_2terms_triangular(rng, BENCH_MAX_DEGREE);
+    let b = make_random_monomial(rng, BENCH_MAX_DEGREE);
+    let c = make_random_2terms_triangular(rng, BENCH_MAX_DEGREE);
+    [a, b, c]
+}
+
+fn monomial_triangular_monomial<const N: usize>(
+    rng: &mut impl Rng,
+) -> [TropicalAutomorphism<N>; 3] {
+    let a = make_random_monomial(rng, BENCH_MAX_DEGREE);
+    let b = make_random_2terms_triangular(rng, BENCH_MAX_DEGREE);
+    let c = make_random_monomial(rng, BENCH_MAX_DEGREE);
+    [a, b, c]
+}
+
 #[bench]
 fn bench_3d_monomial_triangular_monomial_composition(bencher: &mut Bencher) {
-    bencher.iter(|| {
-        let _ = compose_monomial_triangular_monomial::<3>();
-    });
+    let automorphisms = monomial_triangular_monomial::<3>(&mut StdRng::seed_from_u64(BENCH_SEED));
+    bench_composition(bencher, automorphisms);
 }
 
 #[bench]
 fn bench_5d_monomial_triangular_monomial_composition(bencher: &mut Bencher) {
-    bencher.iter(|| {
-        let _ = compose_monomial_triangular_monomial::<5>();
-    });
+    let automorphisms = monomial_triangular_monomial::<5>(&mut StdRng::seed_from_u64(BENCH_SEED));
+    bench_composition(bencher, automorphisms);
 }
 
 #[bench]
 fn bench_3d_triangular_monomial_triangular_composition(bencher: &mut Bencher) {
-    bencher.iter(|| {
-        let _ = compose_triangular_monomial_triangular::<3>();
-    });
+    let automorphisms = triangular_monomial_triangular::<3>(&mut StdRng::seed_from_u64(BENCH_SEED));
+    bench_composition(bencher, automorphisms);
 }
 
 #[bench]
 fn bench_5d_triangular_monomial_triangular_composition(bencher: &mut Bencher) {
-    bencher.iter(|| {
-        let _ = compose_triangular_monomial_triangular::<5>();
-    });
+    let automorphisms = triangular_monomial_triangular::<5>(&mut StdRng::seed_from_u64(BENCH_SEED));
+    bench_composition(bencher, automorphisms);
 }
 
 #[bench]
 fn bench_6d_triangular_monomial_triangular_composition(bencher: &mut Bencher) {
-    bencher.iter(|| {
-        let _ = compose_triangular_monomial_triangular::<6>();
-    });
+    let automorphisms = triangular_monomial_triangular::<6>(&mut StdRng::seed_from_u64(BENCH_SEED));
+    bench_composition(bencher, automorphisms);
 }
 
 fn compose_triangular_monomial_triangular<const N: usize>() -> TropicalAutomorphism<N> {
-    let a = make_random_2terms_triangular();
-    let b = make_random_monomial();
-    let c = make_random_2terms_triangular();
-
+    let [a, b, c] = triangular_monomial_triangular(&mut StdRng::seed_from_u64(BENCH_SEED));
     let result = a.compose(b).compose(c);
     println!(
         "({N}d) (triangular o monomial o triangular) {:?}",
@@ -92,9 +125,7 @@ fn compose_triangular_monomial_triangular<const N: usize>() -> TropicalAutomorph
 }
 
 fn compose_monomial_triangular_monomial<const N: usize>() -> TropicalAutomorphism<N> {
-    let a = make_random_monomial();
-    let b = make_random_2terms_triangular();
-    let c = make_random_monomial();
+    let [a, b, c] = monomial_triangular_monomial(&mut StdRng::seed_from_u64(BENCH_SEED));
     let result = a.compose(b).compose(c);
     println!(
         "({N}d) (monomial o triangular o monomial) {:?}",
@@ -108,11 +139,12 @@ fn compose_monomial_triangular_monomial<const N: usize>() -> TropicalAutomorphis
 }
 
 fn compose_public_key<const N: usize>() -> TropicalAutomorphism<N> {
-    let a = make_random_monomial();
-    let b = make_random_2terms_triangular();
-    let c = make_random_monomial();
-    let d = make_random_2terms_triangular();
-    let e = make_random_monomial();
+    let rng = &mut StdRng::seed_from_u64(BENCH_SEED);
+    let a = make_random_monomial(rng, BENCH_MAX_DEGREE);
+    let b = make_random_2terms_triangular(rng, BENCH_MAX_DEGREE);
+    let c = make_random_monomial(rng, BENCH_MAX_DEGREE);
+    let d = make_random_2terms_triangular(rng, BENCH_MAX_DEGREE);
+    let e = make_random_monomial(rng, BENCH_MAX_DEGREE);
     let result = a.compose(b.compose(c)).compose(d.compose(e));
     println!(
         "(4d) (monomial o triangular o monomial o triangular o monomial) {:?}",
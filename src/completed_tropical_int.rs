@@ -0,0 +1,332 @@
+/*
+    README: max-plus integers completed with +∞. the usual conventions for the completed
+    semiring are that -∞ absorbs everything in ⊙ (so -∞ ⊙ +∞ = -∞) and division is
+    residuation, b / a is the largest x with x ⊙ a <= b. that's b - a for integers and it's
+    defined everywhere: b / -∞ = +∞, so rational functions have a value even where their
+    denominator vanishes. ±∞ have no actual inverse, that's why `inverse` gives None for them.
+*/
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
+use std::iter::Sum;
+
+use crate::error::TropicalError;
+use crate::semiring::{CheckedSemiring, Semifield, Semiring};
+use crate::tropical_int::TropicalInt;
+use crate::tropical_polynomial::{
+    checked_narrow_degree, narrow_degree, Degree, TropicalPolynomial,
+};
+use crate::tropical_rational::TropicalRational;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompletedTropicalInt {
+    AdditiveIdentity,
+    Integer(i64),
+    PositiveInfinity,
+}
+
+impl CompletedTropicalInt {
+    pub fn new(value: i64) -> Self {
+        Self::Integer(value)
+    }
+
+    pub fn zero() -> Self {
+        Self::from(0)
+    }
+
+    /// `self \ rhs`, the largest `x` with `self ⊙ x <= rhs`.
+    pub fn left_residual(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (Self::AdditiveIdentity, _) | (_, Self::PositiveInfinity) => Self::PositiveInfinity,
+            (Self::PositiveInfinity, _) | (_, Self::AdditiveIdentity) => Self::AdditiveIdentity,
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            (Self::Integer(a), Self::Integer(b)) => Self::Integer(b - a),
+        }
+    }
+
+    /// `rhs / self`, the largest `x` with `x ⊙ self <= rhs`. The same as `left_residual`
+    /// since ⊙ commutes.
+    pub fn right_residual(self, rhs: Self) -> Self {
+        self.left_residual(rhs)
+    }
+}
+
+impl Semiring for CompletedTropicalInt {
+    fn additive_identity() -> Self {
+        Self::AdditiveIdentity
+    }
+
+    fn multiplicative_identity() -> Self {
+        Self::zero()
+    }
+
+    // README: x^0 = 0 even for ±∞ and negative powers swap the infinities, like 0 / x does
+    fn pow(&self, power: Degree) -> Self {
+        match (self, power.cmp(&0)) {
+            (_, Ordering::Equal) => Self::zero(),
+            (Self::Integer(int), _) => Self::Integer(int * narrow_degree(power)),
+            (infinity, Ordering::Greater) => *infinity,
+            (Self::AdditiveIdentity, Ordering::Less) => Self::PositiveInfinity,
+            (_, Ordering::Less) => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl CheckedSemiring for CompletedTropicalInt {
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        match (self, rhs) {
+            (Self::Integer(a), Self::Integer(b)) => a
+                .checked_add(*b)
+                .map(Self::Integer)
+                .ok_or(TropicalError::Overflow),
+            _ => Ok(*self * *rhs),
+        }
+    }
+
+    fn checked_pow(&self, power: Degree) -> Result<Self, TropicalError> {
+        match self {
+            Self::Integer(int) if power != 0 => Degree::from(*int)
+                .checked_mul(power)
+                .ok_or(TropicalError::Overflow)
+                .and_then(checked_narrow_degree)
+                .map(Self::Integer),
+            _ => Ok(self.pow(power)),
+        }
+    }
+
+    // README: the residual is defined everywhere, only the integers can overflow
+    fn checked_div(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        match (self, rhs) {
+            (Self::Integer(a), Self::Integer(b)) => a
+                .checked_sub(*b)
+                .map(Self::Integer)
+                .ok_or(TropicalError::Overflow),
+            _ => Ok(*self / *rhs),
+        }
+    }
+}
+
+impl Semifield for CompletedTropicalInt {
+    fn inverse(&self) -> Option<Self> {
+        match self {
+            Self::Integer(int) => Some(Self::Integer(-int)),
+            _ => None,
+        }
+    }
+
+    fn root(&self, index: Degree) -> Option<Self> {
+        if index == 0 {
+            return None;
+        }
+        match self {
+            Self::Integer(int) => {
+                let index = checked_narrow_degree(index).ok()?;
+                (int % index == 0).then(|| Self::Integer(int / index))
+            }
+            infinity => Some(*infinity),
+        }
+    }
+}
+
+impl PartialOrd for CompletedTropicalInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompletedTropicalInt {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        match (self, rhs) {
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::AdditiveIdentity, Self::AdditiveIdentity)
+            | (Self::PositiveInfinity, Self::PositiveInfinity) => Ordering::Equal,
+            (Self::AdditiveIdentity, _) | (_, Self::PositiveInfinity) => Ordering::Less,
+            (_, Self::AdditiveIdentity) | (Self::PositiveInfinity, _) => Ordering::Greater,
+        }
+    }
+}
+
+impl Add for CompletedTropicalInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.max(rhs)
+    }
+}
+
+impl AddAssign for CompletedTropicalInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul for CompletedTropicalInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (Self::AdditiveIdentity, _) | (_, Self::AdditiveIdentity) => Self::AdditiveIdentity,
+            (Self::PositiveInfinity, _) | (_, Self::PositiveInfinity) => Self::PositiveInfinity,
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            (Self::Integer(a), Self::Integer(b)) => Self::Integer(a + b),
+        }
+    }
+}
+
+impl MulAssign for CompletedTropicalInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for CompletedTropicalInt {
+    type Output = CompletedTropicalInt;
+    fn div(self, rhs: Self) -> Self::Output {
+        rhs.right_residual(self)
+    }
+}
+
+impl DivAssign for CompletedTropicalInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl fmt::Display for CompletedTropicalInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(a) => write!(f, "{a}"),
+            Self::AdditiveIdentity => write!(f, "-∞"),
+            Self::PositiveInfinity => write!(f, "+∞"),
+        }
+    }
+}
+
+impl From<i64> for CompletedTropicalInt {
+    fn from(value: i64) -> Self {
+        CompletedTropicalInt::new(value)
+    }
+}
+
+impl From<TropicalInt> for CompletedTropicalInt {
+    fn from(value: TropicalInt) -> Self {
+        match value {
+            TropicalInt::Integer(int) => Self::Integer(int),
+            TropicalInt::AdditiveIdentity => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl Sum for CompletedTropicalInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(CompletedTropicalInt::AdditiveIdentity, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> From<TropicalPolynomial<N, TropicalInt>>
+    for TropicalPolynomial<N, CompletedTropicalInt>
+{
+    fn from(poly: TropicalPolynomial<N, TropicalInt>) -> Self {
+        poly.map_coefficients(|c| CompletedTropicalInt::from(*c))
+    }
+}
+
+impl<const N: usize> From<TropicalRational<N, TropicalInt>>
+    for TropicalRational<N, CompletedTropicalInt>
+{
+    fn from(rational: TropicalRational<N, TropicalInt>) -> Self {
+        TropicalRational::new(rational.numerator.into(), rational.denominator.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompletedTropicalInt;
+    use crate::{
+        error::TropicalError,
+        semiring::{CheckedSemiring, Semifield, Semiring},
+        tropical_int::TropicalInt,
+        tropical_polynomial::TropicalPolynomial,
+        tropical_rational::TropicalRational,
+    };
+
+    #[test]
+    fn test_residuation() {
+        let bottom = CompletedTropicalInt::AdditiveIdentity;
+        let top = CompletedTropicalInt::PositiveInfinity;
+        let test_table: Vec<[CompletedTropicalInt; 4]> = vec![
+            // a, b, a ⊙ b, a \ b
+            [
+                CompletedTropicalInt::from(3),
+                CompletedTropicalInt::from(5),
+                CompletedTropicalInt::from(8),
+                CompletedTropicalInt::from(2),
+            ],
+            [CompletedTropicalInt::from(3), bottom, bottom, bottom],
+            [bottom, CompletedTropicalInt::from(3), bottom, top],
+            [bottom, bottom, bottom, top],
+            [top, CompletedTropicalInt::from(3), top, bottom],
+            [CompletedTropicalInt::from(3), top, top, top],
+            [top, bottom, bottom, bottom],
+            [top, top, top, top],
+        ];
+
+        for [a, b, product, residual] in test_table {
+            assert_eq!(a * b, product);
+            assert_eq!(a.left_residual(b), residual);
+            assert_eq!(b / a, residual);
+            assert_eq!(b.checked_div(&a), Ok(residual));
+            // the residual is the largest solution of a ⊙ x <= b
+            assert!(a * residual <= b);
+        }
+
+        assert_eq!(bottom.pow(0), CompletedTropicalInt::zero());
+        assert_eq!(bottom.pow(-2), top);
+        assert_eq!(top.pow(3), top);
+        assert_eq!(top.root(2), Some(top));
+        assert_eq!(CompletedTropicalInt::from(3).root(0), None);
+        assert_eq!(
+            CompletedTropicalInt::from(i64::MIN).checked_div(&CompletedTropicalInt::from(1)),
+            Err(TropicalError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_vanishing_denominator() {
+        // (x + y) / x is undefined at x = -∞ over `TropicalInt`
+        let rational: TropicalRational<2> = TropicalRational::new(
+            TropicalPolynomial::variable(0) + TropicalPolynomial::variable(1),
+            TropicalPolynomial::variable(0),
+        );
+        let rational = TropicalRational::<2, CompletedTropicalInt>::from(rational);
+
+        let test_table: Vec<([CompletedTropicalInt; 2], CompletedTropicalInt)> = vec![
+            (
+                [TropicalInt::from(1), TropicalInt::from(4)].map(CompletedTropicalInt::from),
+                CompletedTropicalInt::from(3),
+            ),
+            (
+                [
+                    CompletedTropicalInt::AdditiveIdentity,
+                    CompletedTropicalInt::from(4),
+                ],
+                CompletedTropicalInt::PositiveInfinity,
+            ),
+            (
+                [CompletedTropicalInt::AdditiveIdentity; 2],
+                CompletedTropicalInt::PositiveInfinity,
+            ),
+            (
+                [
+                    CompletedTropicalInt::from(2),
+                    CompletedTropicalInt::PositiveInfinity,
+                ],
+                CompletedTropicalInt::PositiveInfinity,
+            ),
+        ];
+
+        for (point, value) in test_table {
+            assert_eq!(rational.evaluate(point), Ok(value));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt;
+
+use crate::tropical_polynomial::Degree;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TropicalError {
+    /// The degrees matrix has determinant 0, so the monomial map isn't invertible.
+    SingularMatrix,
+    /// The inverse degrees matrix would have non integer entries.
+    NotUnimodular { determinant: Degree },
+    /// A monomial with a -∞ coefficient can't be undone.
+    InfiniteCoefficient,
+    /// The point has no integer preimage, or the search for it got stuck.
+    NoPreimage,
+    /// A coefficient or a degree doesn't fit in its integer type.
+    Overflow,
+    /// Division by the additive identity (-∞ in max-plus).
+    DivisionByAdditiveIdentity,
+    /// Floats have to be numbers below +∞.
+    NotANumber,
+}
+
+impl fmt::Display for TropicalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SingularMatrix => write!(f, "degrees matrix is singular"),
+            Self::NotUnimodular { determinant } => write!(
+                f,
+                "degrees matrix has determinant {determinant}, it needs to be 1 or -1"
+            ),
+            Self::InfiniteCoefficient => write!(f, "coefficient is -∞"),
+            Self::NoPreimage => write!(f, "no preimage found"),
+            Self::Overflow => write!(f, "integer overflow"),
+            Self::DivisionByAdditiveIdentity => write!(f, "division by the additive identity"),
+            Self::NotANumber => write!(f, "float is NaN or +∞"),
+        }
+    }
+}
+
+impl std::error::Error for TropicalError {}
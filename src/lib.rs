@@ -1,9 +1,20 @@
-#![feature(new_range_api)]
 #![feature(test)]
 extern crate test;
 
+pub mod completed_tropical_int;
+pub mod error;
+mod linear_program;
+pub mod min_plus_int;
+pub mod monomial_order;
+pub mod semiring;
+mod terms;
 pub mod tropical_automorphism;
+pub mod tropical_big_int;
+pub mod tropical_factorization;
+pub mod tropical_float;
+pub mod tropical_fraction;
 pub mod tropical_int;
+pub mod tropical_matrix;
 pub mod tropical_polynomial;
 pub mod tropical_rational;
 
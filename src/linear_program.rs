@@ -0,0 +1,257 @@
+/*
+    README: small exact simplex solver, we need it to decide which terms of a
+    tropical polynomial lie on the upper convex hull of the lifted support.
+    it's a dense tableau over big rationals with Bland's rule, so it's slow but
+    it never cycles and never rounds, which is what we care about for normal forms.
+*/
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum LinearProgram {
+    Infeasible,
+    Unbounded,
+    Optimal {
+        value: BigRational,
+        point: Vec<BigRational>,
+    },
+}
+
+pub(crate) fn rational(value: impl Into<BigInt>) -> BigRational {
+    BigRational::from_integer(value.into())
+}
+
+struct Tableau {
+    // every row is [coefficients..., right hand side]
+    rows: Vec<Vec<BigRational>>,
+    basis: Vec<usize>,
+    reduced_costs: Vec<BigRational>,
+    value: BigRational,
+}
+
+impl Tableau {
+    fn columns(&self) -> usize {
+        self.reduced_costs.len()
+    }
+
+    fn pivot(&mut self, row: usize, column: usize) {
+        let pivot = self.rows[row][column].clone();
+        for entry in self.rows[row].iter_mut() {
+            *entry /= pivot.clone();
+        }
+
+        let pivot_row = self.rows[row].clone();
+        for (index, other) in self.rows.iter_mut().enumerate() {
+            if index == row || other[column].is_zero() {
+                continue;
+            }
+            let factor = other[column].clone();
+            for (entry, pivot_entry) in other.iter_mut().zip(pivot_row.iter()) {
+                *entry -= factor.clone() * pivot_entry;
+            }
+        }
+
+        let factor = self.reduced_costs[column].clone();
+        if !factor.is_zero() {
+            for (cost, pivot_entry) in self.reduced_costs.iter_mut().zip(pivot_row.iter()) {
+                *cost -= factor.clone() * pivot_entry;
+            }
+            self.value += factor * pivot_row.last().unwrap();
+        }
+
+        self.basis[row] = column;
+    }
+
+    fn set_objective(&mut self, costs: &[BigRational]) {
+        self.reduced_costs = costs.to_vec();
+        self.value = BigRational::zero();
+
+        for (row, &basic) in self.rows.iter().zip(self.basis.iter()) {
+            let cost = costs[basic].clone();
+            if cost.is_zero() {
+                continue;
+            }
+            for (reduced_cost, entry) in self.reduced_costs.iter_mut().zip(row.iter()) {
+                *reduced_cost -= cost.clone() * entry;
+            }
+            self.value += cost * row.last().unwrap();
+        }
+    }
+
+    // README: only the first `allowed` columns may enter the basis, which is how we
+    // keep artificial variables out during the second phase
+    fn optimize(&mut self, allowed: usize) -> bool {
+        loop {
+            let Some(entering) = (0..allowed).find(|&j| self.reduced_costs[j].is_positive()) else {
+                return true;
+            };
+
+            let leaving = self
+                .rows
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row[entering].is_positive())
+                .map(|(index, row)| {
+                    (
+                        row.last().unwrap() / &row[entering],
+                        self.basis[index],
+                        index,
+                    )
+                })
+                .min_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            match leaving {
+                Some((_, _, row)) => self.pivot(row, entering),
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Maximizes `costs · x` subject to `constraints x = bounds` and `x >= 0`.
+pub(crate) fn maximize(
+    constraints: &[Vec<BigRational>],
+    bounds: &[BigRational],
+    costs: &[BigRational],
+) -> LinearProgram {
+    let variables = costs.len();
+    let artificials = constraints.len();
+
+    let rows = constraints
+        .iter()
+        .zip(bounds.iter())
+        .enumerate()
+        .map(|(index, (constraint, bound))| {
+            debug_assert_eq!(constraint.len(), variables);
+            let sign = if bound.is_negative() {
+                -rational(1)
+            } else {
+                rational(1)
+            };
+
+            let mut row: Vec<BigRational> = constraint.iter().map(|a| a * &sign).collect();
+            row.extend((0..artificials).map(|j| {
+                if j == index {
+                    rational(1)
+                } else {
+                    BigRational::zero()
+                }
+            }));
+            row.push(bound * &sign);
+            row
+        })
+        .collect();
+
+    let mut tableau = Tableau {
+        rows,
+        basis: (variables..variables + artificials).collect(),
+        reduced_costs: vec![],
+        value: BigRational::zero(),
+    };
+
+    // phase one: minimize the artificial variables
+    let phase_one_costs: Vec<BigRational> = (0..variables + artificials)
+        .map(|j| {
+            if j < variables {
+                BigRational::zero()
+            } else {
+                -rational(1)
+            }
+        })
+        .collect();
+    tableau.set_objective(&phase_one_costs);
+    tableau.optimize(tableau.columns());
+    if tableau.value.is_negative() {
+        return LinearProgram::Infeasible;
+    }
+
+    // drive the remaining (zero valued) artificial variables out of the basis
+    let mut row = 0;
+    while row < tableau.rows.len() {
+        if tableau.basis[row] >= variables {
+            match (0..variables).find(|&j| !tableau.rows[row][j].is_zero()) {
+                Some(column) => tableau.pivot(row, column),
+                None => {
+                    // redundant constraint
+                    tableau.rows.remove(row);
+                    tableau.basis.remove(row);
+                    continue;
+                }
+            }
+        }
+        row += 1;
+    }
+
+    let mut phase_two_costs = costs.to_vec();
+    phase_two_costs.extend((0..artificials).map(|_| BigRational::zero()));
+    tableau.set_objective(&phase_two_costs);
+    if !tableau.optimize(variables) {
+        return LinearProgram::Unbounded;
+    }
+
+    let mut point = vec![BigRational::zero(); variables];
+    for (row, &basic) in tableau.rows.iter().zip(tableau.basis.iter()) {
+        if basic < variables {
+            point[basic] = row.last().unwrap().clone();
+        }
+    }
+
+    LinearProgram::Optimal {
+        value: tableau.value,
+        point,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{maximize, rational, LinearProgram};
+
+    #[test]
+    fn test_maximize() {
+        /*
+            max x + y
+            x + 2y + s = 4
+            3x + y + t = 6
+            optimum at (8/5, 6/5)
+        */
+        let constraints = vec![
+            vec![rational(1), rational(2), rational(1), rational(0)],
+            vec![rational(3), rational(1), rational(0), rational(1)],
+        ];
+        let bounds = vec![rational(4), rational(6)];
+        let costs = vec![rational(1), rational(1), rational(0), rational(0)];
+
+        match maximize(&constraints, &bounds, &costs) {
+            LinearProgram::Optimal { value, point } => {
+                assert_eq!(value, rational(14) / rational(5));
+                assert_eq!(point[0], rational(8) / rational(5));
+                assert_eq!(point[1], rational(6) / rational(5));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_infeasible_and_unbounded() {
+        // x + y = -1 with x, y >= 0
+        assert_eq!(
+            maximize(
+                &[vec![rational(1), rational(1)]],
+                &[rational(-1)],
+                &[rational(1), rational(0)],
+            ),
+            LinearProgram::Infeasible
+        );
+
+        // x - y = 1, maximize x
+        assert_eq!(
+            maximize(
+                &[vec![rational(1), rational(-1)]],
+                &[rational(1)],
+                &[rational(1), rational(0)],
+            ),
+            LinearProgram::Unbounded
+        );
+    }
+}
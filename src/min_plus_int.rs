@@ -0,0 +1,321 @@
+/*
+    README: min-plus integers, ⊕ is min and the additive identity is +∞. negation is a
+    semiring isomorphism between max-plus and min-plus (max(a, b) = -min(-a, -b)), so the
+    conversions negate every coefficient and a converted polynomial q satisfies
+    q(x) = -p(-x), i.e. evaluating at the converted point gives the converted value.
+    `to_rational` gives the negated value, that way the convex hull algorithms (which think
+    in max-plus) work unchanged on min-plus polynomials.
+    so a `MinPlusInt` is just the `TropicalInt` it maps to, only the order and the printed
+    value look at the sign.
+*/
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
+use std::iter::Sum;
+
+use num_rational::BigRational;
+
+use crate::error::TropicalError;
+use crate::semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar};
+use crate::tropical_int::TropicalInt;
+use crate::tropical_polynomial::{Degree, TropicalPolynomial};
+use crate::tropical_rational::TropicalRational;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MinPlusInt(TropicalInt);
+
+impl MinPlusInt {
+    /// Panics for `i64::MIN`, whose max-plus counterpart doesn't fit in an i64.
+    pub fn new(value: i64) -> Self {
+        Self(TropicalInt::new(
+            value
+                .checked_neg()
+                .expect("i64::MIN has no max-plus counterpart"),
+        ))
+    }
+
+    pub fn zero() -> Self {
+        Self::from(0)
+    }
+
+    pub fn pow<D>(&self, power: D) -> Self
+    where
+        D: Into<i64>,
+    {
+        Self(self.0.pow(power))
+    }
+}
+
+impl Semiring for MinPlusInt {
+    fn additive_identity() -> Self {
+        Self(TropicalInt::AdditiveIdentity)
+    }
+
+    fn multiplicative_identity() -> Self {
+        Self::zero()
+    }
+
+    fn pow(&self, power: Degree) -> Self {
+        Self(Semiring::pow(&self.0, power))
+    }
+}
+
+impl CheckedSemiring for MinPlusInt {
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        self.0.checked_mul(rhs.0).map(Self)
+    }
+
+    fn checked_pow(&self, power: Degree) -> Result<Self, TropicalError> {
+        self.0.checked_pow(power).map(Self)
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        self.0.checked_div(rhs.0).map(Self)
+    }
+}
+
+impl Semifield for MinPlusInt {
+    fn inverse(&self) -> Option<Self> {
+        self.0.inverse().map(Self)
+    }
+
+    fn root(&self, index: Degree) -> Option<Self> {
+        self.0.root(index).map(Self)
+    }
+}
+
+impl TropicalScalar for MinPlusInt {
+    fn to_rational(&self) -> Option<BigRational> {
+        self.0.to_rational()
+    }
+
+    // README: the largest max-plus value below `value` is the smallest min-plus one above -value
+    fn from_rational_floor(value: &BigRational) -> Self {
+        Self(TropicalInt::from_rational_floor(value))
+    }
+}
+
+impl PartialOrd for MinPlusInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// README: the usual order on the integers, so +∞ is the largest and ⊕ keeps the smallest
+impl Ord for MinPlusInt {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        rhs.0.cmp(&self.0)
+    }
+}
+
+impl Add for MinPlusInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for MinPlusInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul for MinPlusInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl MulAssign for MinPlusInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for MinPlusInt {
+    type Output = MinPlusInt;
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs == Self::additive_identity() {
+            // README: use `checked_div` to get an error instead
+            panic!("div by +inf");
+        }
+        Self(self.0 / rhs.0)
+    }
+}
+
+impl DivAssign for MinPlusInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl fmt::Display for MinPlusInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            // README: the max-plus i64::MIN is a min-plus value past i64::MAX
+            TropicalInt::Integer(a) => write!(f, "{}", -i128::from(a)),
+            TropicalInt::AdditiveIdentity => write!(f, "+∞"),
+        }
+    }
+}
+
+impl From<i64> for MinPlusInt {
+    fn from(value: i64) -> Self {
+        MinPlusInt::new(value)
+    }
+}
+
+impl Sum for MinPlusInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(MinPlusInt::additive_identity(), |a, b| a + b)
+    }
+}
+
+impl From<TropicalInt> for MinPlusInt {
+    fn from(value: TropicalInt) -> Self {
+        Self(value)
+    }
+}
+
+impl From<MinPlusInt> for TropicalInt {
+    fn from(value: MinPlusInt) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> From<TropicalPolynomial<N, TropicalInt>>
+    for TropicalPolynomial<N, MinPlusInt>
+{
+    fn from(poly: TropicalPolynomial<N, TropicalInt>) -> Self {
+        poly.map_coefficients(|c| MinPlusInt::from(*c))
+    }
+}
+
+impl<const N: usize> From<TropicalPolynomial<N, MinPlusInt>>
+    for TropicalPolynomial<N, TropicalInt>
+{
+    fn from(poly: TropicalPolynomial<N, MinPlusInt>) -> Self {
+        poly.map_coefficients(|c| TropicalInt::from(*c))
+    }
+}
+
+impl<const N: usize> From<TropicalRational<N, TropicalInt>> for TropicalRational<N, MinPlusInt> {
+    fn from(rational: TropicalRational<N, TropicalInt>) -> Self {
+        TropicalRational::new(rational.numerator.into(), rational.denominator.into())
+    }
+}
+
+impl<const N: usize> From<TropicalRational<N, MinPlusInt>> for TropicalRational<N, TropicalInt> {
+    fn from(rational: TropicalRational<N, MinPlusInt>) -> Self {
+        TropicalRational::new(rational.numerator.into(), rational.denominator.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinPlusInt;
+    use crate::{
+        error::TropicalError,
+        semiring::{CheckedSemiring, Semiring},
+        tropical_int::TropicalInt,
+        tropical_polynomial::TropicalPolynomial,
+        tropical_rational::TropicalRational,
+    };
+
+    #[test]
+    fn test_min_plus() {
+        let test_table: Vec<[MinPlusInt; 4]> = vec![
+            // a, b, a + b, a * b
+            [
+                MinPlusInt::from(3),
+                MinPlusInt::from(5),
+                MinPlusInt::from(3),
+                MinPlusInt::from(8),
+            ],
+            [
+                MinPlusInt::from(-1),
+                MinPlusInt::additive_identity(),
+                MinPlusInt::from(-1),
+                MinPlusInt::additive_identity(),
+            ],
+        ];
+
+        for [a, b, sum, product] in test_table {
+            assert_eq!(a + b, sum);
+            assert_eq!(a * b, product);
+            assert_eq!(a.min(b), sum);
+            assert_eq!(
+                TropicalInt::from(a) + TropicalInt::from(b),
+                TropicalInt::from(sum)
+            );
+        }
+    }
+
+    #[test]
+    fn test_duality() {
+        let max_plus: TropicalPolynomial<2> = TropicalPolynomial::from(vec![
+            ([2, 0], TropicalInt::from(0)),
+            ([1, 1], TropicalInt::from(-1)),
+            ([0, 1], TropicalInt::from(4)),
+            // not a vertex of the upper hull, it shouldn't be one of the lower hull either
+            ([1, 0], TropicalInt::from(-5)),
+        ]);
+        let min_plus = TropicalPolynomial::<2, MinPlusInt>::from(max_plus.clone());
+
+        assert_eq!(min_plus.get_term(&[0, 1]), Some(&MinPlusInt::from(-4)));
+        assert_eq!(TropicalPolynomial::<2>::from(min_plus.clone()), max_plus);
+        assert_eq!(
+            TropicalPolynomial::<2>::from(min_plus.normal_form()),
+            max_plus.normal_form()
+        );
+
+        for point in [[0, 0], [3, -2], [-7, 1]] {
+            let point = point.map(TropicalInt::from);
+            assert_eq!(
+                min_plus.evaluate(point.map(MinPlusInt::from)),
+                MinPlusInt::from(max_plus.evaluate(point))
+            );
+        }
+
+        let rational: TropicalRational<2> = TropicalRational::new(
+            max_plus.clone(),
+            TropicalPolynomial::variable(0) + TropicalPolynomial::multiplicative_identity(),
+        );
+        let point = [TropicalInt::from(2), TropicalInt::from(-3)];
+        assert_eq!(
+            TropicalRational::<2, MinPlusInt>::from(rational.clone())
+                .evaluate(point.map(MinPlusInt::from)),
+            rational.evaluate(point).map(MinPlusInt::from)
+        );
+        assert_eq!(
+            MinPlusInt::multiplicative_identity(),
+            MinPlusInt::from(TropicalInt::multiplicative_identity())
+        );
+        assert_eq!(MinPlusInt::from(-4).to_string(), "-4");
+        assert_eq!(MinPlusInt::additive_identity().to_string(), "+∞");
+        assert_eq!(
+            MinPlusInt::from(TropicalInt::from(i64::MIN)).to_string(),
+            "9223372036854775808"
+        );
+    }
+
+    #[test]
+    fn test_checked_div() {
+        assert_eq!(
+            MinPlusInt::from(3).checked_div(&MinPlusInt::from(5)),
+            Ok(MinPlusInt::from(-2))
+        );
+        assert_eq!(
+            MinPlusInt::from(3).checked_div(&MinPlusInt::additive_identity()),
+            Err(TropicalError::DivisionByAdditiveIdentity)
+        );
+        assert_eq!(
+            MinPlusInt::from(-i64::MAX).checked_div(&MinPlusInt::from(1)),
+            Err(TropicalError::Overflow)
+        );
+    }
+}
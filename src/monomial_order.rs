@@ -0,0 +1,87 @@
+use core::cmp::Ordering;
+
+use crate::tropical_polynomial::Degree;
+
+/// Total orders on the multi degrees that are compatible with multiplication, i.e.
+/// `a < b` implies `a + c < b + c`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MonomialOrder {
+    /// The first variable with a different degree decides.
+    Lex,
+    /// The total degree decides, ties are broken by `Lex`.
+    GradedLex,
+    /// The total degree decides, ties go to the smaller degree in the last variable that
+    /// differs.
+    GradedReverseLex,
+}
+
+impl MonomialOrder {
+    pub fn compare<const N: usize>(&self, a: &[Degree; N], b: &[Degree; N]) -> Ordering {
+        let total_degree = |multi_degree: &[Degree; N]| multi_degree.iter().sum::<Degree>();
+
+        match self {
+            Self::Lex => a.cmp(b),
+            Self::GradedLex => total_degree(a).cmp(&total_degree(b)).then_with(|| a.cmp(b)),
+            Self::GradedReverseLex => total_degree(a).cmp(&total_degree(b)).then_with(|| {
+                a.iter()
+                    .zip(b.iter())
+                    .rev()
+                    .map(|(a, b)| b.cmp(a))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use super::MonomialOrder;
+    use crate::tropical_polynomial::Degree;
+
+    #[test]
+    fn test_monomial_orders() {
+        // a, b, lex, graded lex, graded reverse lex
+        let test_table: Vec<([Degree; 3], [Degree; 3], [Ordering; 3])> = vec![
+            (
+                [1, 2, 0],
+                [0, 3, 4],
+                [Ordering::Greater, Ordering::Less, Ordering::Less],
+            ),
+            (
+                [1, 5, 2],
+                [4, 1, 3],
+                [Ordering::Less, Ordering::Less, Ordering::Greater],
+            ),
+            (
+                [3, 0, 0],
+                [1, 1, 1],
+                [Ordering::Greater, Ordering::Greater, Ordering::Greater],
+            ),
+            (
+                [1, 1, 0],
+                [1, 0, 1],
+                [Ordering::Greater, Ordering::Greater, Ordering::Greater],
+            ),
+            (
+                [2, -1, 0],
+                [2, -1, 0],
+                [Ordering::Equal, Ordering::Equal, Ordering::Equal],
+            ),
+        ];
+
+        for (a, b, orderings) in test_table {
+            let orders = [
+                MonomialOrder::Lex,
+                MonomialOrder::GradedLex,
+                MonomialOrder::GradedReverseLex,
+            ];
+            for (order, ordering) in orders.iter().zip(orderings) {
+                assert_eq!(order.compare(&a, &b), ordering);
+                assert_eq!(order.compare(&b, &a), ordering.reverse());
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+/*
+    README: the scalars polynomials, rationals and automorphisms are generic over.
+    ⊕ is `Add` and ⊙ is `Mul`, so the max-plus `TropicalInt` reads the same as before.
+    `TropicalScalar` is the extra bit the convex hull algorithms (normal forms, division,
+    simplification) need: a way to see the scalar as a max-plus value in the rationals.
+*/
+use core::fmt;
+use core::ops::{Add, Div, Mul};
+
+use num_rational::BigRational;
+
+use crate::error::TropicalError;
+use crate::tropical_polynomial::Degree;
+
+pub trait Semiring:
+    Clone
+    + PartialEq
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Mul<Output = Self>
+    + Send
+    + Sync
+{
+    fn additive_identity() -> Self;
+
+    fn multiplicative_identity() -> Self;
+
+    fn is_additive_identity(&self) -> bool {
+        *self == Self::additive_identity()
+    }
+
+    /// `self ⊙ ... ⊙ self`, negative powers only make sense in a semifield.
+    fn pow(&self, power: Degree) -> Self {
+        debug_assert!(power >= 0, "negative powers need a semifield");
+        (0..power).fold(Self::multiplicative_identity(), |acc, _| acc * self.clone())
+    }
+}
+
+/// ⊙ and powers that fail with `TropicalError::Overflow` instead of wrapping around, the
+/// defaults are for scalars that can't overflow.
+pub trait CheckedSemiring: Semiring {
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        Ok(self.clone() * rhs.clone())
+    }
+
+    fn checked_pow(&self, power: Degree) -> Result<Self, TropicalError> {
+        Ok(self.pow(power))
+    }
+
+    /// `self / rhs` with `TropicalError::DivisionByAdditiveIdentity` where `Div` panics.
+    fn checked_div(&self, rhs: &Self) -> Result<Self, TropicalError>
+    where
+        Self: Semifield,
+    {
+        if rhs.is_additive_identity() {
+            return Err(TropicalError::DivisionByAdditiveIdentity);
+        }
+        Ok(self.clone() / rhs.clone())
+    }
+}
+
+pub trait Semifield: CheckedSemiring + Div<Output = Self> {
+    /// The multiplicative inverse, `None` for the additive identity.
+    fn inverse(&self) -> Option<Self>;
+
+    /// `self^(1/index)`, `None` when that isn't a scalar (like the square root of 3 in the
+    /// integers).
+    fn root(&self, index: Degree) -> Option<Self>;
+}
+
+pub trait TropicalScalar: Semifield {
+    /// The max-plus value of the scalar, `None` for the additive identity. Min-plus scalars
+    /// are negated so the larger value is always the one ⊕ keeps.
+    fn to_rational(&self) -> Option<BigRational>;
+
+    /// The largest scalar whose max-plus value is at most `value`.
+    fn from_rational_floor(value: &BigRational) -> Self;
+}
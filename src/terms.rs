@@ -0,0 +1,410 @@
+// README: only one of the stores backs `TropicalPolynomial` depending on the `sorted-terms`
+// feature, the other one is still compiled so we can test them side by side
+#![cfg_attr(not(feature = "sorted-terms"), allow(dead_code))]
+
+use core::cmp::{Ordering, Reverse};
+use core::ops::Index;
+use std::collections::{BinaryHeap, HashMap};
+
+use rayon::prelude::*;
+
+use crate::semiring::Semiring;
+use crate::tropical_polynomial::Degree;
+
+#[cfg(not(feature = "sorted-terms"))]
+pub(crate) type Terms<const N: usize, T> = HashMap<[Degree; N], T>;
+#[cfg(feature = "sorted-terms")]
+pub(crate) type Terms<const N: usize, T> = SortedTerms<N, T>;
+
+// README: below this many products the threads cost more than they save
+const PARALLEL_PRODUCTS: usize = 1 << 12;
+
+/// The arithmetic a polynomial needs from the map of `multi_degree -> coefficient`.
+pub(crate) trait TermStore<const N: usize, T>: Default + Send + Sync + Sized {
+    /// Collects the terms, keeping the sum of the coefficients of repeated degrees.
+    fn from_terms(terms: Vec<([Degree; N], T)>) -> Self;
+    fn merge(self, rhs: Self) -> Self;
+    /// Omits the products that are the additive identity.
+    fn product(&self, rhs: &Self) -> Self;
+    /// Splits the terms into at most `parts` stores of about the same size.
+    fn split(&self, parts: usize) -> Vec<Self>;
+    fn len(&self) -> usize;
+
+    /// `product` with the terms of `self` partitioned between the threads, every thread
+    /// multiplies its part by all of `rhs` and the partial products are merged.
+    fn par_product(&self, rhs: &Self) -> Self {
+        let threads = rayon::current_num_threads();
+        if threads == 1 || self.len() * rhs.len() < PARALLEL_PRODUCTS {
+            return self.product(rhs);
+        }
+
+        self.split(threads)
+            .into_par_iter()
+            .map(|part| part.product(rhs))
+            .reduce(Self::default, Self::merge)
+    }
+}
+
+fn add_degrees<const N: usize>(a: &[Degree; N], b: &[Degree; N]) -> [Degree; N] {
+    core::array::from_fn(|i| a[i] + b[i])
+}
+
+impl<const N: usize, T: Semiring> TermStore<N, T> for HashMap<[Degree; N], T> {
+    fn from_terms(terms: Vec<([Degree; N], T)>) -> Self {
+        let mut result = HashMap::new();
+        for (multi_degree, coefficient) in terms {
+            let current_coefficient = result
+                .get(&multi_degree)
+                .cloned()
+                .unwrap_or_else(T::additive_identity);
+
+            if coefficient.clone() + current_coefficient == coefficient {
+                result.insert(multi_degree, coefficient);
+            }
+        }
+
+        result
+    }
+
+    fn merge(mut self, rhs: Self) -> Self {
+        for (exponents, coefficient) in rhs {
+            let current_coefficient = self.entry(exponents).or_insert_with(T::additive_identity);
+            *current_coefficient = current_coefficient.clone() + coefficient;
+        }
+
+        self
+    }
+
+    fn product(&self, rhs: &Self) -> Self {
+        let mut result = HashMap::new();
+
+        for (exponents1, coefficient1) in self.iter() {
+            for (exponents2, coefficient2) in rhs.iter() {
+                let coefficient_increment = coefficient1.clone() * coefficient2.clone();
+
+                // README: it's probably slower to branch but we don't want to have terms in the map with coefficient equal to the additive identity, prefering to omit them.
+                if coefficient_increment.is_additive_identity() {
+                    continue;
+                }
+
+                result
+                    .entry(add_degrees(exponents1, exponents2))
+                    .and_modify(|current_coefficient: &mut T| {
+                        *current_coefficient =
+                            current_coefficient.clone() + coefficient_increment.clone()
+                    })
+                    .or_insert(coefficient_increment);
+            }
+        }
+
+        result
+    }
+
+    fn split(&self, parts: usize) -> Vec<Self> {
+        let terms: Vec<_> = self.iter().collect();
+        terms
+            .chunks(terms.len().div_ceil(parts).max(1))
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|(multi_degree, coefficient)| (**multi_degree, (*coefficient).clone()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+/*
+    README: the terms in increasing lex order of the degrees, which is a monomial order, i.e.
+    `a < b` implies `a + c < b + c`. so sums are a merge of two sorted lists, and for
+    products every row `a_i * b_0, a_i * b_1, ...` is already sorted, we merge the rows
+    with a heap holding the next product of each one (johnson's algorithm). lookups are
+    binary searches and iteration order is deterministic.
+*/
+#[derive(Clone, Debug)]
+pub(crate) struct SortedTerms<const N: usize, T> {
+    terms: Vec<([Degree; N], T)>,
+}
+
+impl<const N: usize, T> Default for SortedTerms<N, T> {
+    fn default() -> Self {
+        SortedTerms { terms: Vec::new() }
+    }
+}
+
+impl<const N: usize, T> SortedTerms<N, T> {
+    fn position(&self, multi_degree: &[Degree; N]) -> Result<usize, usize> {
+        self.terms
+            .binary_search_by(|(current, _)| current.cmp(multi_degree))
+    }
+
+    pub(crate) fn get(&self, multi_degree: &[Degree; N]) -> Option<&T> {
+        self.position(multi_degree)
+            .ok()
+            .map(|index| &self.terms[index].1)
+    }
+
+    pub(crate) fn insert(&mut self, multi_degree: [Degree; N], coefficient: T) -> Option<T> {
+        match self.position(&multi_degree) {
+            Ok(index) => Some(core::mem::replace(&mut self.terms[index].1, coefficient)),
+            Err(index) => {
+                self.terms.insert(index, (multi_degree, coefficient));
+                None
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&[Degree; N], &T)> {
+        self.terms
+            .iter()
+            .map(|(multi_degree, coefficient)| (multi_degree, coefficient))
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &[Degree; N]> {
+        self.terms.iter().map(|(multi_degree, _)| multi_degree)
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &T> {
+        self.terms.iter().map(|(_, coefficient)| coefficient)
+    }
+}
+
+impl<const N: usize, T: Semiring> TermStore<N, T> for SortedTerms<N, T> {
+    fn from_terms(mut terms: Vec<([Degree; N], T)>) -> Self {
+        terms.sort_by_key(|(multi_degree, _)| *multi_degree);
+
+        let mut result: Vec<([Degree; N], T)> = Vec::with_capacity(terms.len());
+        for (multi_degree, coefficient) in terms {
+            match result.last_mut() {
+                Some((last, current)) if *last == multi_degree => {
+                    *current = current.clone() + coefficient
+                }
+                _ => result.push((multi_degree, coefficient)),
+            }
+        }
+
+        SortedTerms { terms: result }
+    }
+
+    fn merge(self, rhs: Self) -> Self {
+        let mut terms = Vec::with_capacity(self.len().max(rhs.len()));
+        let mut lhs = self.terms.into_iter().peekable();
+        let mut rhs = rhs.terms.into_iter().peekable();
+
+        loop {
+            let next = match (lhs.peek(), rhs.peek()) {
+                (Some((a, _)), Some((b, _))) => match a.cmp(b) {
+                    Ordering::Less => lhs.next(),
+                    Ordering::Greater => rhs.next(),
+                    Ordering::Equal => lhs
+                        .next()
+                        .zip(rhs.next())
+                        .map(|((multi_degree, a), (_, b))| (multi_degree, a + b)),
+                },
+                (Some(_), None) => lhs.next(),
+                (None, _) => rhs.next(),
+            };
+
+            match next {
+                Some(term) => terms.push(term),
+                None => break,
+            }
+        }
+
+        SortedTerms { terms }
+    }
+
+    fn product(&self, rhs: &Self) -> Self {
+        let mut terms: Vec<([Degree; N], T)> = Vec::new();
+        if rhs.is_empty() {
+            return SortedTerms { terms };
+        }
+
+        // README: (degree of a_i * b_j, i, j), reversed so the heap pops the smallest degree
+        let mut heap: BinaryHeap<_> = self
+            .terms
+            .iter()
+            .enumerate()
+            .map(|(i, (multi_degree, _))| {
+                Reverse((add_degrees(multi_degree, &rhs.terms[0].0), i, 0))
+            })
+            .collect();
+
+        while let Some(Reverse((multi_degree, i, j))) = heap.pop() {
+            if let Some((next_degree, _)) = rhs.terms.get(j + 1) {
+                heap.push(Reverse((
+                    add_degrees(&self.terms[i].0, next_degree),
+                    i,
+                    j + 1,
+                )));
+            }
+
+            let coefficient = self.terms[i].1.clone() * rhs.terms[j].1.clone();
+            if coefficient.is_additive_identity() {
+                continue;
+            }
+
+            match terms.last_mut() {
+                Some((last, current)) if *last == multi_degree => {
+                    *current = current.clone() + coefficient
+                }
+                _ => terms.push((multi_degree, coefficient)),
+            }
+        }
+
+        SortedTerms { terms }
+    }
+
+    fn split(&self, parts: usize) -> Vec<Self> {
+        self.terms
+            .chunks(self.terms.len().div_ceil(parts).max(1))
+            .map(|chunk| SortedTerms {
+                terms: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.terms.len()
+    }
+}
+
+impl<const N: usize, T> Index<&[Degree; N]> for SortedTerms<N, T> {
+    type Output = T;
+
+    fn index(&self, multi_degree: &[Degree; N]) -> &T {
+        self.get(multi_degree).expect("term not found")
+    }
+}
+
+impl<const N: usize, T> IntoIterator for SortedTerms<N, T> {
+    type Item = ([Degree; N], T);
+    type IntoIter = std::vec::IntoIter<([Degree; N], T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.terms.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{SortedTerms, TermStore};
+    use crate::{tropical_int::TropicalInt, tropical_polynomial::Degree};
+
+    type Terms = Vec<([Degree; 2], i64)>;
+
+    fn terms(terms: Terms) -> Vec<([Degree; 2], TropicalInt)> {
+        terms
+            .into_iter()
+            .map(|(multi_degree, coefficient)| (multi_degree, TropicalInt::from(coefficient)))
+            .collect()
+    }
+
+    fn sorted(terms: &SortedTerms<2, TropicalInt>) -> Vec<([Degree; 2], TropicalInt)> {
+        terms.iter().map(|(d, c)| (*d, *c)).collect()
+    }
+
+    fn hashed(terms: &HashMap<[Degree; 2], TropicalInt>) -> Vec<([Degree; 2], TropicalInt)> {
+        let mut terms: Vec<_> = terms.iter().map(|(d, c)| (*d, *c)).collect();
+        terms.sort_by_key(|(multi_degree, _)| *multi_degree);
+        terms
+    }
+
+    #[test]
+    fn test_sorted_terms() {
+        // lhs, rhs, lhs + rhs, lhs * rhs
+        let test_table: Vec<[Terms; 4]> = vec![
+            [
+                vec![([1, 0], 2), ([0, 1], 3), ([1, 0], 5)],
+                vec![([0, 0], 1), ([0, 1], 0)],
+                vec![([0, 0], 1), ([0, 1], 3), ([1, 0], 5)],
+                vec![([0, 1], 4), ([0, 2], 3), ([1, 0], 6), ([1, 1], 5)],
+            ],
+            [vec![([2, -1], 0)], vec![], vec![([2, -1], 0)], vec![]],
+            [
+                vec![([0, 0], 0), ([1, 1], 0)],
+                vec![([0, 0], 0), ([1, 1], 0)],
+                vec![([0, 0], 0), ([1, 1], 0)],
+                vec![([0, 0], 0), ([1, 1], 0), ([2, 2], 0)],
+            ],
+        ];
+
+        for [lhs, rhs, sum, product] in test_table {
+            let sorted_lhs = SortedTerms::from_terms(terms(lhs.clone()));
+            let sorted_rhs = SortedTerms::from_terms(terms(rhs.clone()));
+            let hashed_lhs = HashMap::from_terms(terms(lhs));
+            let hashed_rhs = HashMap::from_terms(terms(rhs));
+
+            assert_eq!(
+                sorted(&sorted_lhs.product(&sorted_rhs)),
+                terms(product.clone())
+            );
+            assert_eq!(hashed(&hashed_lhs.product(&hashed_rhs)), terms(product));
+            assert_eq!(sorted(&sorted_lhs.merge(sorted_rhs)), terms(sum.clone()));
+            assert_eq!(hashed(&hashed_lhs.merge(hashed_rhs)), terms(sum));
+        }
+    }
+
+    #[test]
+    fn test_sorted_terms_lookup() {
+        let mut store = SortedTerms::from_terms(terms(vec![([3, 0], 1), ([0, 3], 2)]));
+
+        assert_eq!(store.insert([1, 1], TropicalInt::from(4)), None);
+        assert_eq!(
+            store.insert([3, 0], TropicalInt::from(0)),
+            Some(TropicalInt::from(1))
+        );
+        assert_eq!(store.get(&[1, 1]), Some(&TropicalInt::from(4)));
+        assert_eq!(store.get(&[1, 2]), None);
+        assert_eq!(store[&[0, 3]], TropicalInt::from(2));
+        assert_eq!(
+            store.keys().copied().collect::<Vec<_>>(),
+            vec![[0, 3], [1, 1], [3, 0]]
+        );
+    }
+
+    #[test]
+    fn test_par_product() {
+        // 100 * 100 products, over the threshold
+        let lhs = terms(
+            (0..100)
+                .map(|i| ([Degree::from(i % 7), Degree::from(i)], i % 5))
+                .collect(),
+        );
+        let rhs = terms(
+            (0..100)
+                .map(|i| ([Degree::from(i), Degree::from(i % 3)], -(i % 11)))
+                .collect(),
+        );
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+
+        let sorted_lhs = SortedTerms::from_terms(lhs.clone());
+        let sorted_rhs = SortedTerms::from_terms(rhs.clone());
+        let hashed_lhs = HashMap::from_terms(lhs);
+        let hashed_rhs = HashMap::from_terms(rhs);
+
+        let product = sorted(&sorted_lhs.product(&sorted_rhs));
+        pool.install(|| {
+            assert_eq!(sorted_lhs.split(4).len(), 4);
+            assert_eq!(sorted(&sorted_lhs.par_product(&sorted_rhs)), product);
+            assert_eq!(hashed(&hashed_lhs.par_product(&hashed_rhs)), product);
+        });
+    }
+}
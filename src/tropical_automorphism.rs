@@ -1,22 +1,70 @@
+use core::convert::Infallible;
 use core::fmt;
+use std::collections::{HashMap, HashSet};
 
+use num_rational::BigRational;
 use rayon::prelude::*;
 
 use crate::{
+    error::TropicalError,
+    linear_program::rational,
+    semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar},
     tropical_int::TropicalInt,
-    tropical_polynomial::{Degree, TropicalPolynomial},
+    tropical_polynomial::{gcd, Degree, TropicalPolynomial},
     tropical_rational::TropicalRational,
 };
 
-#[derive(PartialEq, Clone, Debug)]
-pub struct TropicalAutomorphism<const N: usize> {
-    pub(crate) mappings: [TropicalRational<N>; N],
-    // TODO: inverse: [TropicalRational<N>; N],
+/*
+    README: the inverse is only known when the automorphism is built from the elementary
+    ones (identity, scalar, invertible monomial, triangular). we keep those layers instead
+    of the symbolic inverse since composing the inverses blows up: `compose` concatenates
+    them, `inverse()` composes their inverses in reverse order on demand, and
+    `evaluate_inverse` and `invert_at` undo them one point at a time without building
+    anything. `new` can't know the layers.
+
+    inverses of monomials with det(M) != ±1 have rational degrees, we keep them over the
+    common denominator `root` so every mapping is really mapping^(1/root). tropically
+    (a + b)^r = a^r + b^r, so that's closed under composition and the degrees stay integers.
+*/
+#[derive(Clone, Debug)]
+pub struct TropicalAutomorphism<const N: usize, T = TropicalInt> {
+    pub(crate) mappings: [TropicalRational<N, T>; N],
+    pub(crate) layers: Option<Vec<Layer<N, T>>>,
+    pub(crate) root: Degree,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Layer<const N: usize, T> {
+    Scalar(T),
+    // README: the degrees can be negative here, inverse monomials are layers too.
+    // it's x -> (Mx + c) / root in classical coordinates
+    Monomial {
+        degrees_matrix: [[Degree; N]; N],
+        coefficients: [T; N],
+        root: Degree,
+    },
+    Triangular {
+        variable: usize,
+        row: TropicalPolynomial<N, T>,
+        inverted: bool,
+    },
 }
 
-impl<const N: usize> TropicalAutomorphism<N> {
-    pub fn new(mappings: [TropicalRational<N>; N]) -> Self {
-        Self { mappings }
+impl<const N: usize, T: TropicalScalar> TropicalAutomorphism<N, T> {
+    pub fn new(mappings: [TropicalRational<N, T>; N]) -> Self {
+        Self {
+            mappings,
+            layers: None,
+            root: 1,
+        }
+    }
+
+    fn with_layer(mappings: [TropicalRational<N, T>; N], layer: Layer<N, T>) -> Self {
+        Self {
+            mappings,
+            layers: Some(vec![layer]),
+            root: 1,
+        }
     }
 
     pub fn identity() -> Self {
@@ -24,27 +72,77 @@ impl<const N: usize> TropicalAutomorphism<N> {
             mappings: core::array::from_fn(|index| {
                 TropicalRational::polynomial(TropicalPolynomial::variable(index))
             }),
+            layers: Some(vec![]),
+            root: 1,
         }
     }
 
-    pub fn scalar(factor: TropicalInt) -> Self {
-        Self {
-            mappings: core::array::from_fn(|index| {
-                TropicalRational::polynomial(
-                    TropicalPolynomial::variable(index)
-                        * TropicalPolynomial::constant(factor.clone()),
-                )
-            }),
+    pub fn scalar(factor: T) -> Self {
+        let mappings = core::array::from_fn(|index| {
+            TropicalRational::polynomial(
+                TropicalPolynomial::variable(index) * TropicalPolynomial::constant(factor.clone()),
+            )
+        });
+
+        if factor.is_additive_identity() {
+            Self::new(mappings)
+        } else {
+            Self::with_layer(mappings, Layer::Scalar(factor))
         }
     }
 
-    pub fn monomial(degrees_matrix: [[Degree; N]; N], coefficients: [TropicalInt; N]) -> Self {
+    /// The inverse, when this automorphism was built from invertible elementary ones.
+    pub fn inverse(&self) -> Option<Self> {
+        self.layers.as_ref().map(|layers| {
+            layers.iter().rev().fold(Self::identity(), |acc, layer| {
+                acc.compose(layer.inverse().automorphism())
+            })
+        })
+    }
+
+    /// The common denominator of the degrees, 1 unless there are radicals.
+    pub fn root(&self) -> Degree {
+        self.root
+    }
+
+    /// Maps a point through the automorphism as a function `T^N -> T^N`. With radicals the
+    /// degrees have the denominator `root`, which is `TropicalError::NotUnimodular` when a
+    /// value has no root in `T` (see `TropicalFraction` for those), and a denominator that
+    /// is -∞ at the point is `TropicalError::DivisionByAdditiveIdentity`.
+    pub fn evaluate(&self, point: [T; N]) -> Result<[T; N], TropicalError> {
+        let values = self
+            .mappings
+            .iter()
+            .map(|mapping| {
+                mapping.evaluate(point.clone())?.root(self.root).ok_or(
+                    TropicalError::NotUnimodular {
+                        determinant: self.root,
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(values.try_into().unwrap())
+    }
+
+    /// Maps a point through the inverse, undoing the layers one at a time. `None` when the
+    /// inverse isn't known or the point has no preimage in `T`, see `invert_at` for the errors
+    /// and for automorphisms built with `new`.
+    pub fn evaluate_inverse(&self, point: [T; N]) -> Option<[T; N]> {
+        self.layers
+            .as_ref()?
+            .iter()
+            .try_fold(point, |point, layer| layer.invert_at(point).ok())
+    }
+
+    pub fn monomial(degrees_matrix: [[Degree; N]; N], coefficients: [T; N]) -> Self {
         debug_assert!(
             degrees_matrix.iter().all(|row| row.iter().all(|d| *d >= 0)),
             "monomials must have positive degrees"
         );
 
-        // TODO: assert degrees_matrix has determinant != 0
+        // README: singular matrices are fine for experiments, they just have no inverse
+        let invertible = determinant(&degrees_matrix) != 0
+            && !coefficients.iter().any(|c| c.is_additive_identity());
         Self {
             mappings: core::array::from_fn(|index| {
                 TropicalRational::polynomial(TropicalPolynomial::from(vec![(
@@ -52,10 +150,18 @@ impl<const N: usize> TropicalAutomorphism<N> {
                     coefficients[index].clone(),
                 )]))
             }),
+            layers: invertible.then(|| {
+                vec![Layer::Monomial {
+                    degrees_matrix,
+                    coefficients,
+                    root: 1,
+                }]
+            }),
+            root: 1,
         }
     }
 
-    pub fn elementary_triangular(variable: usize, row: TropicalPolynomial<N>) -> Self {
+    pub fn elementary_triangular(variable: usize, row: TropicalPolynomial<N, T>) -> Self {
         debug_assert!(variable < N);
         debug_assert!(row
             .terms
@@ -66,12 +172,19 @@ impl<const N: usize> TropicalAutomorphism<N> {
             TropicalRational::polynomial(TropicalPolynomial::variable(index))
         });
 
-        mappings[variable] *= TropicalRational::polynomial(row);
+        mappings[variable] *= TropicalRational::polynomial(row.clone());
 
-        Self { mappings }
+        Self::with_layer(
+            mappings,
+            Layer::Triangular {
+                variable,
+                row,
+                inverted: false,
+            },
+        )
     }
 
-    pub fn inverse_elementary_triangular(variable: usize, row: TropicalPolynomial<N>) -> Self {
+    pub fn inverse_elementary_triangular(variable: usize, row: TropicalPolynomial<N, T>) -> Self {
         assert!(variable < N);
         assert!(row.terms.iter().all(|(multi_degree, _)| {
             multi_degree.iter().take(variable).all(|d| *d == 0) && multi_degree[variable] == 0
@@ -81,67 +194,600 @@ impl<const N: usize> TropicalAutomorphism<N> {
             TropicalRational::polynomial(TropicalPolynomial::variable(index))
         });
 
-        mappings[variable] /= TropicalRational::polynomial(row);
+        mappings[variable] /= TropicalRational::polynomial(row.clone());
 
-        Self { mappings }
+        Self::with_layer(
+            mappings,
+            Layer::Triangular {
+                variable,
+                row,
+                inverted: true,
+            },
+        )
     }
 
-    pub fn apply(&self, poly: &TropicalPolynomial<N>) -> TropicalPolynomial<N> {
-        poly.terms.iter().fold(
-            TropicalPolynomial::new(),
-            |acc, (multi_degree, coefficient)| {
-                acc + multi_degree.iter().enumerate().fold(
-                    TropicalPolynomial::constant(coefficient.clone()),
-                    |term, (index, degree)| term * self.mappings[index].numerator.pow(*degree),
+    /*
+        README: every term becomes c prod_i (n_i / d_i)^k_i, we put all of them over the common
+        denominator prod_i d_i^K_i where K_i is the largest degree of x_i in the polynomial, so
+        each term only needs its numerator multiplied by the missing d_i^(K_i - k_i).
+        polynomial mappings have d_i = 0 and skip all of that.
+        with radicals p((n / d)^(1/k)) isn't a rational function, so we return its k-th power
+        p^(k)(n / d) instead where p^(k) has every coefficient to the k.
+        `mul` and `pow` are the polynomial products, `apply` and `try_apply` only differ in
+        whether those check for overflows.
+    */
+    fn image_with<E, M, P>(
+        &self,
+        poly: &TropicalPolynomial<N, T>,
+        mul: &M,
+        pow: &P,
+    ) -> Result<TropicalRational<N, T>, E>
+    where
+        E: Send,
+        M: Fn(
+                &TropicalPolynomial<N, T>,
+                &TropicalPolynomial<N, T>,
+            ) -> Result<TropicalPolynomial<N, T>, E>
+            + Sync,
+        P: Fn(&TropicalPolynomial<N, T>, Degree) -> Result<TropicalPolynomial<N, T>, E> + Sync,
+    {
+        let identity = TropicalPolynomial::multiplicative_identity();
+        let is_polynomial = |index: usize| self.mappings[index].denominator == identity;
+        let max_degrees: [Degree; N] =
+            core::array::from_fn(|i| poly.terms.keys().map(|d| d[i]).max().unwrap_or(0));
+
+        // README: the factor of x_i^k in the image of a term, once for every distinct k
+        let factors: Vec<HashMap<Degree, TropicalPolynomial<N, T>>> = (0..N)
+            .into_par_iter()
+            .map(|index| {
+                let mapping = &self.mappings[index];
+                let degrees: HashSet<Degree> = poly.terms.keys().map(|d| d[index]).collect();
+                degrees
+                    .into_par_iter()
+                    .map(|degree| {
+                        let factor = pow(&mapping.numerator, degree)?;
+                        if is_polynomial(index) {
+                            Ok((degree, factor))
+                        } else {
+                            let missing = pow(&mapping.denominator, max_degrees[index] - degree)?;
+                            Ok((degree, mul(&factor, &missing)?))
+                        }
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, E>>()?;
+
+        let mut terms = poly
+            .terms
+            .iter()
+            .map(|(multi_degree, coefficient)| {
+                let coefficient = TropicalPolynomial::constant(coefficient.clone());
+                if self.root == 1 {
+                    Ok((*multi_degree, coefficient))
+                } else {
+                    Ok((*multi_degree, pow(&coefficient, self.root)?))
+                }
+            })
+            .collect::<Result<Vec<_>, E>>()?;
+        terms.sort_by_key(|(multi_degree, _)| *multi_degree);
+        let numerator = image_of_terms(&terms, 0, &identity, &factors, mul)?;
+
+        let denominator = (0..N).filter(|&index| !is_polynomial(index)).try_fold(
+            identity.clone(),
+            |acc, index| {
+                mul(
+                    &acc,
+                    &pow(&self.mappings[index].denominator, max_degrees[index])?,
                 )
-                // .reduce(
-                //     || TropicalPolynomial::multiplicative_identity(),
-                //     |a, b| a * b,
-                // )
             },
-        )
+        )?;
+
+        Ok(TropicalRational::new(numerator, denominator))
     }
 
+    fn image(&self, poly: &TropicalPolynomial<N, T>) -> TropicalRational<N, T> {
+        let Ok(image) =
+            self.image_with::<Infallible, _, _>(poly, &|lhs, rhs| Ok(lhs * rhs), &|poly, power| {
+                Ok(poly.pow(power))
+            });
+        image
+    }
+
+    /// The image of `poly`, which has a denominator when some mapping does. When `root` isn't
+    /// 1 the image of a radical isn't a rational function, so this is the `root`-th power of
+    /// the image instead.
+    pub fn apply(&self, poly: &TropicalPolynomial<N, T>) -> TropicalRational<N, T> {
+        self.image(poly)
+    }
+
+    /// Same as `apply`, also the `root`-th power of the image.
+    pub fn apply_rational(&self, rational: &TropicalRational<N, T>) -> TropicalRational<N, T> {
+        if rational.denominator == TropicalPolynomial::multiplicative_identity() {
+            return self.image(&rational.numerator);
+        }
+        self.image(&rational.numerator) / self.image(&rational.denominator)
+    }
+
+    pub fn simplify(&self) -> Self {
+        Self {
+            mappings: core::array::from_fn(|index| self.mappings[index].simplify()),
+            layers: self.layers.clone(),
+            root: self.root,
+        }
+    }
+
+    /// Compares every mapping as a function, see `TropicalRational::functionally_eq`.
+    pub fn functionally_eq(&self, other: &Self) -> bool {
+        self.mappings
+            .iter()
+            .zip(other.mappings.iter())
+            .all(|(lhs, rhs)| {
+                if self.root == other.root {
+                    lhs.functionally_eq(rhs)
+                } else {
+                    lhs.pow(other.root).functionally_eq(&rhs.pow(self.root))
+                }
+            })
+    }
+
+    // README: `apply_rational` already gives the rhs.root-th power, see `image`
     pub fn compose(self, rhs: Self) -> Self {
         Self {
+            root: self
+                .root
+                .checked_mul(rhs.root)
+                .expect("the root doesn't fit in a Degree, use try_compose"),
+            layers: self.composed_layers(&rhs),
             mappings: self
                 .mappings
                 .par_iter()
-                .map(|lhs| {
-                    TropicalRational::new(rhs.apply(&lhs.numerator), lhs.denominator.clone())
-                })
+                .map(|lhs| rhs.apply_rational(lhs))
                 .collect::<Vec<_>>()
                 .try_into()
                 .unwrap(),
         }
     }
+
+    fn composed_layers(&self, rhs: &Self) -> Option<Vec<Layer<N, T>>> {
+        match (&self.layers, &rhs.layers) {
+            (Some(lhs_layers), Some(rhs_layers)) => {
+                Some([lhs_layers.clone(), rhs_layers.clone()].concat())
+            }
+            _ => None,
+        }
+    }
 }
 
-impl TropicalAutomorphism<2> {
+/*
+    README: the terms are sorted, so the ones with the same degrees in x_0, ..., x_(i-1) are
+    contiguous and share the product of those factors, `prefix`. every group with the same
+    degree in x_i multiplies it by the factor of x_i once, and the groups go to different
+    threads.
+*/
+fn image_of_terms<const N: usize, T: Semiring, E: Send>(
+    terms: &[([Degree; N], TropicalPolynomial<N, T>)],
+    index: usize,
+    prefix: &TropicalPolynomial<N, T>,
+    factors: &[HashMap<Degree, TropicalPolynomial<N, T>>],
+    mul: &(impl Fn(
+        &TropicalPolynomial<N, T>,
+        &TropicalPolynomial<N, T>,
+    ) -> Result<TropicalPolynomial<N, T>, E>
+          + Sync),
+) -> Result<TropicalPolynomial<N, T>, E> {
+    if index == N {
+        // the degrees are all fixed by now, so this is a single term
+        return terms
+            .iter()
+            .try_fold(TropicalPolynomial::new(), |acc, (_, coefficient)| {
+                Ok(acc + mul(prefix, coefficient)?)
+            });
+    }
+
+    terms
+        .chunk_by(|a, b| a.0[index] == b.0[index])
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|group| {
+            let factor = &factors[index][&group[0].0[index]];
+            image_of_terms(group, index + 1, &mul(prefix, factor)?, factors, mul)
+        })
+        .try_reduce(TropicalPolynomial::new, |acc, image| Ok(acc + image))
+}
+
+// README: same as `apply` and `compose` but every product is checked for overflows
+impl<const N: usize, T: TropicalScalar + CheckedSemiring> TropicalAutomorphism<N, T> {
+    pub fn try_apply(
+        &self,
+        poly: &TropicalPolynomial<N, T>,
+    ) -> Result<TropicalRational<N, T>, TropicalError> {
+        self.try_image(poly)
+    }
+
+    fn try_image(
+        &self,
+        poly: &TropicalPolynomial<N, T>,
+    ) -> Result<TropicalRational<N, T>, TropicalError> {
+        self.image_with(poly, &|lhs, rhs| lhs.try_mul(rhs), &|poly, power| {
+            poly.try_pow(power)
+        })
+    }
+
+    pub fn try_apply_rational(
+        &self,
+        rational: &TropicalRational<N, T>,
+    ) -> Result<TropicalRational<N, T>, TropicalError> {
+        let numerator = self.try_image(&rational.numerator)?;
+        if rational.denominator == TropicalPolynomial::multiplicative_identity() {
+            return Ok(numerator);
+        }
+        let denominator = self.try_image(&rational.denominator)?;
+
+        Ok(TropicalRational::new(
+            numerator.numerator.try_mul(&denominator.denominator)?,
+            numerator.denominator.try_mul(&denominator.numerator)?,
+        ))
+    }
+
+    pub fn try_compose(self, rhs: Self) -> Result<Self, TropicalError> {
+        Ok(Self {
+            root: self
+                .root
+                .checked_mul(rhs.root)
+                .ok_or(TropicalError::Overflow)?,
+            layers: self.composed_layers(&rhs),
+            mappings: self
+                .mappings
+                .par_iter()
+                .map(|lhs| rhs.try_apply_rational(lhs))
+                .collect::<Result<Vec<_>, _>>()?
+                .try_into()
+                .unwrap(),
+        })
+    }
+}
+
+/*
+    README: a monomial automorphism is the affine map x -> Mx + c in classical coordinates, so
+    its inverse is y -> M⁻¹y - M⁻¹c. M⁻¹ = adj(M) / det(M) only has integer entries when
+    det(M) = ±1, otherwise |det(M)| becomes the root of the inverse. the negative entries
+    become denominators: x^-1 y² is y² / x.
+*/
+impl<const N: usize, T: TropicalScalar> TropicalAutomorphism<N, T> {
     pub fn inverse_monomial(
-        degrees_matrix: [[Degree; 2]; 2],
-        coefficients: [TropicalInt; 2],
-    ) -> Self {
-        let [[a, b], [c, d]] = degrees_matrix;
-        let det = (a * b) - (c * d);
-        debug_assert!(det != 0,
-            "degrees matrix needs to be invertible in order to calculate the monomial automorphism inverse"
-        );
-        debug_assert!(
-            det == 1 || det == -1,
-            "degrees matrix needs to have determinant 1 or -1 in order for the inverse's degrees matrix entries to be integers"
-        );
+        degrees_matrix: [[Degree; N]; N],
+        coefficients: [T; N],
+    ) -> Result<Self, TropicalError> {
+        let det = determinant(&degrees_matrix);
+        if det == 0 {
+            return Err(TropicalError::SingularMatrix);
+        }
+        if coefficients.iter().any(|c| c.is_additive_identity()) {
+            return Err(TropicalError::InfiniteCoefficient);
+        }
 
-        let degrees_matrix_inv = [[det * d, det * -b], [det * -c, det * a]];
+        Ok(Layer::Monomial {
+            degrees_matrix,
+            coefficients,
+            root: 1,
+        }
+        .inverse()
+        .automorphism())
+    }
 
-        Self::monomial(
-            degrees_matrix_inv,
-            [coefficients[0].pow(-1), coefficients[1].pow(-1)],
-        )
+    /*
+        README: finds the preimage of a point without the symbolic inverse. with the layers
+        we undo them one at a time, otherwise we use that the automorphism is piecewise linear:
+        take the linear piece active at a guess, solve its integer system and repeat from the
+        solution until it maps to the point (or we start going around in circles).
+    */
+    pub fn invert_at(&self, point: [T; N]) -> Result<[T; N], TropicalError> {
+        let target = lifted_point(&point)?;
+
+        match &self.layers {
+            Some(layers) => layers
+                .iter()
+                .try_fold(point, |point, layer| layer.invert_at(point)),
+            None => self.invert_piecewise_linear(point, target),
+        }
+    }
+
+    fn invert_piecewise_linear(
+        &self,
+        point: [T; N],
+        target: [BigRational; N],
+    ) -> Result<[T; N], TropicalError> {
+        let mut guess = target.clone();
+        let mut visited = HashSet::new();
+
+        // README: with radicals we solve for root * target, the mappings without their root
+        let target = target.map(|t| t * rational(self.root));
+
+        while visited.insert(guess.clone()) {
+            // mapping i is (d_i - e_i) x + (c_i - f_i) where the numerator term c_i x^d_i and
+            // the denominator term f_i x^e_i are the active ones at the guess
+            let mut matrix = [[0; N]; N];
+            let mut offsets: [BigRational; N] = core::array::from_fn(|_| rational(0));
+            for (index, mapping) in self.mappings.iter().enumerate() {
+                let (numerator_degree, numerator_coefficient) =
+                    active_term(&mapping.numerator, &guess).ok_or(TropicalError::NoPreimage)?;
+                let (denominator_degree, denominator_coefficient) =
+                    active_term(&mapping.denominator, &guess).ok_or(TropicalError::NoPreimage)?;
+
+                matrix[index] =
+                    core::array::from_fn(|i| numerator_degree[i] - denominator_degree[i]);
+                offsets[index] = numerator_coefficient - denominator_coefficient;
+            }
+
+            let det = determinant(&matrix);
+            if det == 0 {
+                return Err(TropicalError::NoPreimage);
+            }
+            let adjugate = adjugate(&matrix);
+            let solution: [BigRational; N] = core::array::from_fn(|i| {
+                (0..N).fold(rational(0), |acc, j| {
+                    acc + rational(adjugate[i][j]) * (&target[j] - &offsets[j])
+                }) / rational(det)
+            });
+
+            // README: a non integer solution can still lead us to the right piece
+            let preimage = solution.clone().map(|x| T::from_rational_floor(&x));
+            if self.evaluate(preimage.clone()).as_ref() == Ok(&point) {
+                return Ok(preimage);
+            }
+            guess = solution.map(|x| x.floor());
+        }
+
+        Err(TropicalError::NoPreimage)
+    }
+}
+
+impl<const N: usize, T: TropicalScalar> Layer<N, T> {
+    fn automorphism(&self) -> TropicalAutomorphism<N, T> {
+        match self {
+            Self::Scalar(factor) => TropicalAutomorphism::scalar(factor.clone()),
+            Self::Monomial {
+                degrees_matrix,
+                coefficients,
+                root,
+            } => TropicalAutomorphism {
+                mappings: core::array::from_fn(|index| {
+                    let row = degrees_matrix[index];
+                    TropicalRational::new(
+                        TropicalPolynomial::monomial(
+                            row.map(|d| d.max(0)),
+                            coefficients[index].clone(),
+                        ),
+                        TropicalPolynomial::monomial(
+                            row.map(|d| (-d).max(0)),
+                            T::multiplicative_identity(),
+                        ),
+                    )
+                }),
+                layers: Some(vec![self.clone()]),
+                root: *root,
+            },
+            Self::Triangular {
+                variable,
+                row,
+                inverted: false,
+            } => TropicalAutomorphism::elementary_triangular(*variable, row.clone()),
+            Self::Triangular {
+                variable,
+                row,
+                inverted: true,
+            } => TropicalAutomorphism::inverse_elementary_triangular(*variable, row.clone()),
+        }
+    }
+
+    fn inverse(&self) -> Self {
+        match self {
+            Self::Scalar(factor) => Self::Scalar(factor.inverse().unwrap()),
+            Self::Monomial {
+                degrees_matrix,
+                coefficients,
+                root,
+            } => {
+                let (degrees_matrix, coefficients, root) =
+                    invert_affine(degrees_matrix, coefficients, *root);
+                Self::Monomial {
+                    degrees_matrix,
+                    coefficients,
+                    root,
+                }
+            }
+            Self::Triangular {
+                variable,
+                row,
+                inverted,
+            } => Self::Triangular {
+                variable: *variable,
+                row: row.clone(),
+                inverted: !inverted,
+            },
+        }
+    }
+
+    fn invert_at(&self, point: [T; N]) -> Result<[T; N], TropicalError> {
+        lifted_point(&point)?;
+
+        match self {
+            Self::Scalar(factor) => Ok(point.map(|p| p / factor.clone())),
+            Self::Monomial {
+                degrees_matrix,
+                coefficients,
+                root,
+            } => {
+                let (degrees_matrix, coefficients, root) =
+                    invert_affine(degrees_matrix, coefficients, *root);
+                let preimage = (0..N)
+                    .map(|index| {
+                        monomial_value(&degrees_matrix[index], &point, coefficients[index].clone())
+                            .root(root)
+                            .ok_or(TropicalError::NoPreimage)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(preimage.try_into().unwrap())
+            }
+            // README: the row doesn't depend on the variable, so we can evaluate it at the image
+            Self::Triangular {
+                variable,
+                row,
+                inverted,
+            } => {
+                let shift = row.evaluate(point.clone());
+                if shift.is_additive_identity() {
+                    return Err(TropicalError::NoPreimage);
+                }
+                let mut preimage = point;
+                preimage[*variable] = if *inverted {
+                    preimage[*variable].clone() * shift
+                } else {
+                    preimage[*variable].clone() / shift
+                };
+                Ok(preimage)
+            }
+        }
+    }
+}
+
+// c x^d with possibly negative degrees, the values have to be finite
+fn monomial_value<const N: usize, T: Semifield>(
+    multi_degree: &[Degree; N],
+    values: &[T; N],
+    coefficient: T,
+) -> T {
+    multi_degree
+        .iter()
+        .zip(values.iter())
+        .fold(coefficient, |acc, (degree, value)| {
+            if *degree >= 0 {
+                acc * value.pow(*degree)
+            } else {
+                acc / value.pow(-degree)
+            }
+        })
+}
+
+// README: x -> (Mx + c) / k is undone by y -> (k adj(M) y - adj(M) c) / det(M), we move the
+// sign of det(M) to the numerator and cancel the common factors of the degrees and the root
+// when the coefficients allow it
+fn invert_affine<const N: usize, T: Semifield>(
+    degrees_matrix: &[[Degree; N]; N],
+    coefficients: &[T; N],
+    root: Degree,
+) -> ([[Degree; N]; N], [T; N], Degree) {
+    let det = determinant(degrees_matrix);
+    debug_assert!(det != 0);
+    let adjugate = adjugate(degrees_matrix).map(|row| row.map(|d| det.signum() * d));
+
+    let degrees_matrix_inv = adjugate.map(|row| row.map(|d| root * d));
+    let coefficients_inv: [T; N] = core::array::from_fn(|index| {
+        T::multiplicative_identity()
+            / monomial_value(&adjugate[index], coefficients, T::multiplicative_identity())
+    });
+    let root_inv = det.abs();
+
+    let common = degrees_matrix_inv
+        .iter()
+        .flatten()
+        .fold(root_inv, |acc, d| gcd(acc, *d));
+    let reduced = coefficients_inv
+        .iter()
+        .map(|c| c.root(common))
+        .collect::<Option<Vec<_>>>();
+    match reduced {
+        Some(reduced) if common > 1 => (
+            degrees_matrix_inv.map(|row| row.map(|d| d / common)),
+            reduced.try_into().unwrap(),
+            root_inv / common,
+        ),
+        _ => (degrees_matrix_inv, coefficients_inv, root_inv),
+    }
+}
+
+fn lifted_point<const N: usize, T: TropicalScalar>(
+    point: &[T; N],
+) -> Result<[BigRational; N], TropicalError> {
+    let lifted = point
+        .iter()
+        .map(|value| value.to_rational().ok_or(TropicalError::NoPreimage))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lifted.try_into().unwrap())
+}
+
+fn active_term<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+    point: &[BigRational; N],
+) -> Option<([Degree; N], BigRational)> {
+    poly.lifted_terms()
+        .into_iter()
+        .map(|(multi_degree, coefficient)| {
+            let value = multi_degree
+                .iter()
+                .zip(point.iter())
+                .fold(coefficient.clone(), |acc, (d, p)| acc + rational(*d) * p);
+            (value, multi_degree, coefficient)
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, multi_degree, coefficient)| (multi_degree, coefficient))
+}
+
+// README: fraction free gaussian elimination (Bareiss), every division is exact
+fn determinant<const N: usize>(matrix: &[[Degree; N]; N]) -> Degree {
+    let mut matrix = matrix.map(|row| row.map(i128::from));
+    let mut sign = 1;
+    let mut previous_pivot = 1;
+
+    for k in 0..N {
+        if matrix[k][k] == 0 {
+            match (k + 1..N).find(|&row| matrix[row][k] != 0) {
+                Some(row) => {
+                    matrix.swap(k, row);
+                    sign = -sign;
+                }
+                None => return 0,
+            }
+        }
+
+        for i in k + 1..N {
+            for j in k + 1..N {
+                matrix[i][j] =
+                    (matrix[i][j] * matrix[k][k] - matrix[i][k] * matrix[k][j]) / previous_pivot;
+            }
+        }
+        previous_pivot = matrix[k][k];
+    }
+
+    if N == 0 {
+        return 1;
     }
+    (sign * matrix[N - 1][N - 1]) as Degree
 }
 
-impl<const N: usize> fmt::Display for TropicalAutomorphism<N> {
+fn adjugate<const N: usize>(matrix: &[[Degree; N]; N]) -> [[Degree; N]; N] {
+    core::array::from_fn(|i| {
+        core::array::from_fn(|j| {
+            // the minor is N x N with row j and column i replaced by a unit vector
+            let mut minor = *matrix;
+            for (row, entries) in minor.iter_mut().enumerate() {
+                entries[i] = Degree::from(row == j);
+            }
+            minor[j] = core::array::from_fn(|column| Degree::from(column == i));
+            determinant(&minor)
+        })
+    })
+}
+
+// README: the layers are only one way of building the mappings, so they don't take part in
+// equality
+impl<const N: usize, T: PartialEq> PartialEq for TropicalAutomorphism<N, T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.mappings == rhs.mappings && self.root == rhs.root
+    }
+}
+
+impl<const N: usize, T: fmt::Display> fmt::Display for TropicalAutomorphism<N, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "(")?;
         for (i, rational) in self.mappings.iter().enumerate() {
@@ -149,6 +795,9 @@ impl<const N: usize> fmt::Display for TropicalAutomorphism<N> {
                 write!(f, ", ")?;
             }
             write!(f, "{}", rational)?;
+            if self.root != 1 {
+                write!(f, "^(1/{})", self.root)?;
+            }
         }
         write!(f, ")")
     }
@@ -156,17 +805,15 @@ impl<const N: usize> fmt::Display for TropicalAutomorphism<N> {
 
 #[cfg(test)]
 mod tests {
-    use rand::{rngs::ThreadRng, Rng};
-    use rayon::prelude::*;
-    use test::Bencher;
-
     use crate::{
+        error::TropicalError,
+        tropical_fraction::{fraction, TropicalFraction},
         tropical_int::TropicalInt,
         tropical_polynomial::{Degree, TropicalPolynomial},
         tropical_rational::TropicalRational,
     };
 
-    use super::TropicalAutomorphism;
+    use super::{adjugate, determinant, TropicalAutomorphism};
 
     #[test]
     fn test_apply_2() {
@@ -241,7 +888,7 @@ mod tests {
         ];
 
         for (alfa, u, v) in test_table {
-            assert_eq!(alfa.apply(&u), v);
+            assert_eq!(alfa.apply(&u), TropicalRational::polynomial(v));
         }
     }
 
@@ -296,7 +943,59 @@ mod tests {
         ];
 
         for (alfa, u, v) in test_table {
-            assert_eq!(alfa.apply(&u), v);
+            assert_eq!(alfa.apply(&u), TropicalRational::polynomial(v));
+        }
+    }
+
+    #[test]
+    fn test_apply_shared_degrees() {
+        let x = TropicalPolynomial::<3>::variable(0);
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+        let auto = TropicalAutomorphism::new([
+            TropicalRational::polynomial(x.clone() * (y.clone() + z.clone())),
+            TropicalRational::new(
+                y.clone(),
+                z.pow(2) + TropicalPolynomial::constant(TropicalInt::from(3)),
+            ),
+            TropicalRational::polynomial(
+                z.clone() + TropicalPolynomial::constant(TropicalInt::from(-1)),
+            ),
+        ]);
+        let test_table: Vec<TropicalPolynomial<3>> = vec![
+            TropicalPolynomial::from(vec![
+                ([2, 1, 0], TropicalInt::from(1)),
+                ([2, 1, 3], TropicalInt::from(-2)),
+                ([2, 0, 1], TropicalInt::from(0)),
+                ([0, 1, 3], TropicalInt::from(4)),
+                ([0, 3, 0], TropicalInt::from(2)),
+            ]),
+            TropicalPolynomial::monomial([1, 2, 3], TropicalInt::from(5)),
+            TropicalPolynomial::new(),
+        ];
+
+        for poly in test_table {
+            // README: every term on its own, c prod_i n_i^k_i d_i^(K_i - k_i)
+            let max_degrees: [Degree; 3] =
+                core::array::from_fn(|i| poly.terms.keys().map(|d| d[i]).max().unwrap_or(0));
+            let numerator = poly.terms.iter().fold(
+                TropicalPolynomial::new(),
+                |acc, (multi_degree, coefficient)| {
+                    acc + (0..3).fold(TropicalPolynomial::constant(*coefficient), |term, i| {
+                        term * auto.mappings[i].numerator.pow(multi_degree[i])
+                            * auto.mappings[i]
+                                .denominator
+                                .pow(max_degrees[i] - multi_degree[i])
+                    })
+                },
+            );
+
+            let image = auto.apply_rational(&TropicalRational::polynomial(poly));
+            assert_eq!(image.numerator, numerator);
+            assert_eq!(
+                image.denominator,
+                auto.mappings[1].denominator.pow(max_degrees[1])
+            );
         }
     }
 
@@ -492,6 +1191,44 @@ mod tests {
         }
     }
 
+    // like `generate_2_terms_key` but with unimodular monomials, so the inverse is tracked
+    fn generate_unimodular_key() -> TropicalAutomorphism<3> {
+        let triangular = TropicalAutomorphism::elementary_triangular(
+            0,
+            TropicalPolynomial::from(vec![
+                ([0, 2, 1], TropicalInt::from(4)),
+                ([0, 1, 3], TropicalInt::from(5)),
+            ]),
+        )
+        .compose(TropicalAutomorphism::elementary_triangular(
+            1,
+            TropicalPolynomial::from(vec![
+                ([0, 0, 2], TropicalInt::from(-1)),
+                ([0, 0, 0], TropicalInt::from(3)),
+            ]),
+        ));
+
+        let monomial_1 = TropicalAutomorphism::monomial(
+            [[1, 1, 0], [0, 1, 1], [1, 1, 1]],
+            [
+                TropicalInt::from(1),
+                TropicalInt::from(3),
+                TropicalInt::from(5),
+            ],
+        );
+
+        let monomial_2 = TropicalAutomorphism::monomial(
+            [[1, 2, 0], [0, 1, 0], [1, 1, 1]],
+            [
+                TropicalInt::from(-5),
+                TropicalInt::from(2),
+                TropicalInt::from(7),
+            ],
+        );
+
+        monomial_1.compose(triangular).compose(monomial_2)
+    }
+
     fn generate_2_terms_key() -> TropicalAutomorphism<3> {
         let triangular_1: TropicalAutomorphism<3> = TropicalAutomorphism::elementary_triangular(
             0,
@@ -577,28 +1314,493 @@ mod tests {
         let cyphertext_v = auto.apply(&v);
         let cyphertext_upv = auto.apply(&upv);
 
-        assert_eq!(cyphertext_u + cyphertext_v, cyphertext_upv);
+        assert!((cyphertext_u + cyphertext_v).functionally_eq(&cyphertext_upv));
+    }
+
+    #[test]
+    fn test_functionally_eq() {
+        let x = TropicalPolynomial::<3>::variable(0);
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+
+        // x -> x(y + z)² and x -> x(y² + z²) are the same automorphism
+        let a = TropicalAutomorphism::new([
+            TropicalRational::polynomial(x.clone() * (y.clone() + z.clone()).pow(2)),
+            TropicalRational::polynomial(y.clone()),
+            TropicalRational::polynomial(z.clone()),
+        ]);
+        let b = TropicalAutomorphism::elementary_triangular(0, y.pow(2) + z.pow(2));
+        let c = TropicalAutomorphism::elementary_triangular(0, y.pow(2) + z.pow(3));
+
+        assert_ne!(a, b);
+        assert!(a.functionally_eq(&b));
+        assert!(!a.functionally_eq(&c));
+    }
+
+    #[test]
+    fn test_inverse_compose_elementary_triangular() {
+        let rows: Vec<TropicalPolynomial<3>> = vec![
+            TropicalPolynomial::monomial([0, 2, 3], TropicalInt::from(4)),
+            TropicalPolynomial::from(vec![
+                ([0, 1, 1], TropicalInt::from(4)),
+                ([0, 3, 2], TropicalInt::from(5)),
+            ]),
+        ];
+
+        for row in rows {
+            let triangular = TropicalAutomorphism::elementary_triangular(0, row.clone());
+            let inverse = TropicalAutomorphism::inverse_elementary_triangular(0, row);
+
+            assert_eq!(
+                inverse.compose(triangular).simplify(),
+                TropicalAutomorphism::identity()
+            );
+        }
+    }
+
+    #[test]
+    fn test_inverse_elementary_triangular() {
+        let rows: Vec<TropicalPolynomial<3>> = vec![
+            TropicalPolynomial::monomial([0, 2, 3], TropicalInt::from(4)),
+            TropicalPolynomial::from(vec![
+                ([0, 1, 1], TropicalInt::from(4)),
+                ([0, 3, 2], TropicalInt::from(5)),
+            ]),
+        ];
+
+        for row in rows {
+            let triangular = TropicalAutomorphism::elementary_triangular(0, row.clone());
+            let inverse = TropicalAutomorphism::inverse_elementary_triangular(0, row);
+
+            assert_eq!(
+                triangular.compose(inverse).simplify(),
+                TropicalAutomorphism::identity()
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_inverse() {
+        let x = TropicalPolynomial::<3>::variable(0);
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+        let constant = |c: i64| TropicalPolynomial::<3>::constant(TropicalInt::from(c));
+
+        let key = TropicalAutomorphism::monomial(
+            [[1, 1, 0], [0, 1, 1], [1, 1, 1]],
+            [
+                TropicalInt::from(1),
+                TropicalInt::from(2),
+                TropicalInt::from(3),
+            ],
+        )
+        .compose(TropicalAutomorphism::elementary_triangular(
+            0,
+            y.clone() * z.clone() + constant(2),
+        ))
+        .compose(TropicalAutomorphism::elementary_triangular(
+            1,
+            z.pow(2) + constant(1),
+        ));
+        let inverse = key.inverse().unwrap();
+
+        let test_table: Vec<TropicalPolynomial<3>> = vec![
+            x.clone() * y.clone() + constant(3) * z.clone(),
+            x.pow(2) + y.clone() * z.clone() + constant(-1),
+        ];
+
+        for poly in test_table {
+            let ciphertext = key.apply(&poly);
+            let plaintext = inverse.apply_rational(&ciphertext);
+
+            assert_eq!(
+                inverse.apply(&poly),
+                inverse.apply_rational(&TropicalRational::polynomial(poly.clone()))
+            );
+            assert!(plaintext.functionally_eq(&TropicalRational::polynomial(poly)));
+        }
+
+        assert!(key
+            .clone()
+            .compose(inverse.clone())
+            .functionally_eq(&TropicalAutomorphism::identity()));
+        assert!(inverse
+            .compose(key)
+            .functionally_eq(&TropicalAutomorphism::identity()));
+    }
+
+    #[test]
+    fn test_determinant_and_adjugate() {
+        assert_eq!(determinant(&[[1, 2], [3, 4]]), -2);
+        assert_eq!(adjugate(&[[1, 2], [3, 4]]), [[4, -2], [-3, 1]]);
+
+        // needs a row swap for the first pivot
+        assert_eq!(determinant(&[[0, 1, 2], [1, 0, 3], [4, -3, 8]]), -2);
+        assert_eq!(determinant(&[[1, 2, 4], [1, 3, 9], [1, 5, 25]]), 6);
+        assert_eq!(determinant(&[[1, 2], [2, 4]]), 0);
+    }
+
+    fn check_inverse_monomial<const N: usize>(
+        degrees_matrix: [[Degree; N]; N],
+        coefficients: [TropicalInt; N],
+    ) {
+        let monomial = TropicalAutomorphism::monomial(degrees_matrix, coefficients);
+        let inverse = TropicalAutomorphism::inverse_monomial(degrees_matrix, coefficients).unwrap();
+
+        // the mappings are (x_i)^(1/root) once undone
+        for (index, mapping) in inverse.mappings.iter().enumerate() {
+            let undone = monomial.apply_rational(mapping);
+            assert_eq!(
+                undone.simplify(),
+                TropicalRational::polynomial(TropicalPolynomial::variable(index).pow(inverse.root))
+            );
+        }
+    }
+
+    #[test]
+    fn test_inverse_monomial() {
+        check_inverse_monomial(
+            [[2, 1], [1, 1]],
+            [TropicalInt::from(1), TropicalInt::from(2)],
+        );
+        check_inverse_monomial(
+            [[1, 2], [1, 1]],
+            [TropicalInt::from(-3), TropicalInt::from(5)],
+        );
+        check_inverse_monomial(
+            [[2, 1], [1, 2]],
+            [TropicalInt::from(3), TropicalInt::from(4)],
+        );
+        check_inverse_monomial(
+            [[2, 0, 1], [0, 3, 0], [1, 1, 1]],
+            [
+                TropicalInt::from(1),
+                TropicalInt::from(-2),
+                TropicalInt::from(3),
+            ],
+        );
+        check_inverse_monomial(
+            [[1, 1, 0], [0, 1, 1], [1, 1, 1]],
+            [
+                TropicalInt::from(1),
+                TropicalInt::from(2),
+                TropicalInt::from(3),
+            ],
+        );
+
+        // x -> 1x²y, y -> 2xy has inverse x -> 1xy⁻¹, y -> -3x⁻¹y²
+        assert_eq!(
+            TropicalAutomorphism::inverse_monomial(
+                [[2, 1], [1, 1]],
+                [TropicalInt::from(1), TropicalInt::from(2)],
+            ),
+            Ok(TropicalAutomorphism::new([
+                TropicalRational::new(
+                    TropicalPolynomial::monomial([1, 0], TropicalInt::from(1)),
+                    TropicalPolynomial::monomial([0, 1], TropicalInt::zero()),
+                ),
+                TropicalRational::new(
+                    TropicalPolynomial::monomial([0, 2], TropicalInt::from(-3)),
+                    TropicalPolynomial::monomial([1, 0], TropicalInt::zero()),
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_inverse_monomial_radical() {
+        // the example from the docs, (3x²y, 4xy²) has inverse ((x² / 2y)^(1/3), (y² / 5x)^(1/3))
+        let degrees_matrix = [[2, 1], [1, 2]];
+        let inverse = TropicalAutomorphism::inverse_monomial(
+            degrees_matrix,
+            [TropicalInt::from(3), TropicalInt::from(4)],
+        );
+        assert_eq!(
+            inverse,
+            Ok(TropicalAutomorphism {
+                mappings: [
+                    TropicalRational::new(
+                        TropicalPolynomial::monomial([2, 0], TropicalInt::from(-2)),
+                        TropicalPolynomial::monomial([0, 1], TropicalInt::zero()),
+                    ),
+                    TropicalRational::new(
+                        TropicalPolynomial::monomial([0, 2], TropicalInt::from(-5)),
+                        TropicalPolynomial::monomial([1, 0], TropicalInt::zero()),
+                    ),
+                ],
+                layers: None,
+                root: 3,
+            })
+        );
+        // with the root 3 applying it gives the cube of the image, x -> x² / 2y
+        assert_eq!(
+            inverse.unwrap().apply(&TropicalPolynomial::variable(0)),
+            TropicalRational::new(
+                TropicalPolynomial::monomial([2, 0], TropicalInt::from(-2)),
+                TropicalPolynomial::monomial([0, 1], TropicalInt::zero()),
+            )
+        );
+
+        let key: TropicalAutomorphism<2, TropicalFraction> = TropicalAutomorphism::monomial(
+            degrees_matrix,
+            [TropicalFraction::from(3), TropicalFraction::from(4)],
+        );
+        let inverse = key.inverse().unwrap();
+        let identity = TropicalAutomorphism::identity();
+        assert_eq!(inverse.root(), 3);
+        assert!(inverse
+            .clone()
+            .compose(key.clone())
+            .functionally_eq(&identity));
+        assert!(key
+            .clone()
+            .compose(inverse.clone())
+            .functionally_eq(&identity));
+
+        let test_table: Vec<[TropicalFraction; 2]> = vec![
+            [TropicalFraction::from(0), TropicalFraction::from(0)],
+            [fraction(1, 3), TropicalFraction::from(-7)],
+            [TropicalFraction::from(5), fraction(-2, 5)],
+        ];
+        for point in test_table {
+            let image = key.evaluate(point.clone()).unwrap();
+            assert_eq!(inverse.evaluate(image.clone()), Ok(point.clone()));
+            assert_eq!(key.invert_at(image.clone()), Ok(point));
+        }
+
+        // over the integers only the images of integer points have a preimage
+        let key: TropicalAutomorphism<2> = TropicalAutomorphism::monomial(
+            degrees_matrix,
+            [TropicalInt::from(3), TropicalInt::from(4)],
+        );
+        let point = [TropicalInt::from(2), TropicalInt::from(-1)];
+        let image = key.evaluate(point).unwrap();
+        assert_eq!(key.invert_at(image), Ok(point));
+        assert_eq!(key.inverse().unwrap().evaluate(image), Ok(point));
+        assert_eq!(
+            key.invert_at([TropicalInt::zero(), TropicalInt::zero()]),
+            Err(TropicalError::NoPreimage)
+        );
+        assert_eq!(
+            key.inverse()
+                .unwrap()
+                .evaluate([TropicalInt::zero(), TropicalInt::zero()]),
+            Err(TropicalError::NotUnimodular { determinant: 3 })
+        );
+        assert_eq!(
+            TropicalAutomorphism::new(key.mappings.clone()).invert_at(image),
+            Ok(point)
+        );
+    }
+
+    #[test]
+    fn test_inverse_monomial_errors() {
+        let coefficients = [TropicalInt::zero(), TropicalInt::zero()];
+
+        assert_eq!(
+            TropicalAutomorphism::inverse_monomial([[1, 2], [2, 4]], coefficients),
+            Err(TropicalError::SingularMatrix)
+        );
+        assert_eq!(
+            TropicalAutomorphism::inverse_monomial(
+                [[1, 0], [0, 1]],
+                [TropicalInt::AdditiveIdentity, TropicalInt::zero()]
+            ),
+            Err(TropicalError::InfiniteCoefficient)
+        );
+    }
+
+    #[test]
+    fn test_inverse_tracking() {
+        let row = TropicalPolynomial::from(vec![
+            ([0, 1, 1], TropicalInt::from(4)),
+            ([0, 3, 2], TropicalInt::from(5)),
+        ]);
+        let triangular = TropicalAutomorphism::elementary_triangular(0, row.clone());
+        let inverse = triangular.inverse().unwrap();
+
+        assert_eq!(
+            inverse,
+            TropicalAutomorphism::inverse_elementary_triangular(0, row)
+        );
+        assert_eq!(inverse.inverse(), Some(triangular.clone()));
+        assert_eq!(
+            inverse.compose(triangular.clone()).simplify(),
+            TropicalAutomorphism::identity()
+        );
+
+        let degrees_matrix = [[1, 1, 0], [0, 1, 1], [1, 1, 1]];
+        let coefficients = [
+            TropicalInt::from(1),
+            TropicalInt::from(2),
+            TropicalInt::from(3),
+        ];
+        let monomial = TropicalAutomorphism::monomial(degrees_matrix, coefficients);
+        let scalar = TropicalAutomorphism::scalar(TropicalInt::from(2));
+        let key = monomial
+            .clone()
+            .compose(triangular.clone())
+            .compose(scalar.clone());
+
+        // (m o t o s)⁻¹ = s⁻¹ o t⁻¹ o m⁻¹
+        assert_eq!(
+            key.inverse(),
+            Some(
+                TropicalAutomorphism::scalar(TropicalInt::from(-2))
+                    .compose(triangular.inverse().unwrap())
+                    .compose(
+                        TropicalAutomorphism::inverse_monomial(degrees_matrix, coefficients)
+                            .unwrap()
+                    )
+            )
+        );
+
+        // no inverse to track with a singular matrix or with `new`
+        let singular =
+            TropicalAutomorphism::monomial([[1, 2, 4], [2, 4, 8], [1, 5, 25]], coefficients);
+        assert_eq!(singular.inverse(), None);
+        assert_eq!(singular.compose(monomial.clone()).inverse(), None);
+        assert_eq!(
+            TropicalAutomorphism::new(monomial.mappings.clone())
+                .compose(monomial)
+                .inverse(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+
+        // x -> 1xy, y -> 2yz, z -> 3xyz then x -> x(yz + 2)
+        let key = TropicalAutomorphism::monomial(
+            [[1, 1, 0], [0, 1, 1], [1, 1, 1]],
+            [
+                TropicalInt::from(1),
+                TropicalInt::from(2),
+                TropicalInt::from(3),
+            ],
+        )
+        .compose(TropicalAutomorphism::elementary_triangular(
+            0,
+            y * z + TropicalPolynomial::constant(TropicalInt::from(2)),
+        ));
+
+        // (1, 2, 3) -> (1 + 5, 2, 3) = (6, 2, 3) -> (1 + 6 + 2, 2 + 2 + 3, 3 + 6 + 2 + 3)
+        let point = [1, 2, 3].map(TropicalInt::from);
+        assert_eq!(key.evaluate(point), Ok([9, 7, 14].map(TropicalInt::from)));
+        assert_eq!(
+            key.evaluate_inverse(key.evaluate(point).unwrap()),
+            Some(point)
+        );
+        assert_eq!(
+            TropicalAutomorphism::new(key.mappings.clone()).evaluate_inverse(point),
+            None
+        );
     }
 
-    // FIXME: inverses will not work until we implement TropicalRational::simplify
-    // #[test]
-    // fn test_inverse_elementary_triangular() {
-    //     let triangular = TropicalAutomorphism::elementary_triangular(
-    //         0,
-    //         TropicalPolynomial::monomial([0, 2, 3], TropicalInt::from(4)),
-    //     );
+    #[test]
+    fn test_evaluate_under_inverse() {
+        let key = generate_unimodular_key();
+        let x = TropicalPolynomial::<3>::variable(0);
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+        let constant = |c: i64| TropicalPolynomial::<3>::constant(TropicalInt::from(c));
 
-    //     let inverse = TropicalAutomorphism::inverse_elementary_triangular(
-    //         0,
-    //         TropicalPolynomial::monomial([0, 2, 3], TropicalInt::from(4)),
-    //     );
+        let p = x.clone() * y.clone() + constant(2) * z.clone() + x * z + constant(-3);
+        let ciphertext = key.apply(&p);
 
-    //     println!("{triangular}");
-    //     println!("{inverse}");
+        // a(p)(a⁻¹(s)) = (p o a o a⁻¹)(s) = p(s)
+        for s in [[0, 0, 0], [1, -2, 5], [7, 3, -4], [-6, 2, 2]] {
+            let s = s.map(TropicalInt::from);
+            let preimage = key.evaluate_inverse(s).unwrap();
+            assert_eq!(ciphertext.evaluate(preimage), Ok(p.evaluate(s)));
+            assert_eq!(key.inverse().unwrap().evaluate(s), Ok(preimage));
+        }
+    }
+
+    #[test]
+    fn test_invert_at() {
+        let key = generate_unimodular_key();
+        // same key but we forget how it was built
+        let forgetful = TropicalAutomorphism::new(key.mappings.clone());
+
+        for x in [[0, 0, 0], [1, -2, 5], [7, 3, -4], [-6, 2, 2], [30, -11, 4]] {
+            let x = x.map(TropicalInt::from);
+            let s = key.evaluate(x).unwrap();
+
+            assert_eq!(key.invert_at(s), Ok(x));
+            assert_eq!(forgetful.invert_at(s), Ok(x));
+        }
 
-    //     assert_eq!(
-    //         triangular.compose(inverse),
-    //         TropicalAutomorphism::identity()
-    //     );
-    // }
+        assert_eq!(
+            key.invert_at([
+                TropicalInt::AdditiveIdentity,
+                TropicalInt::zero(),
+                TropicalInt::zero()
+            ]),
+            Err(TropicalError::NoPreimage)
+        );
+    }
+
+    #[test]
+    fn test_invert_at_not_unimodular() {
+        // x -> x² has no integer preimage for odd values
+        let square = TropicalAutomorphism::<2>::monomial(
+            [[2, 0], [0, 1]],
+            [TropicalInt::zero(), TropicalInt::zero()],
+        );
+
+        assert_eq!(
+            square.invert_at([4, 3].map(TropicalInt::from)),
+            Ok([2, 3].map(TropicalInt::from))
+        );
+        assert_eq!(
+            square.invert_at([5, 3].map(TropicalInt::from)),
+            Err(TropicalError::NoPreimage)
+        );
+    }
+
+    #[test]
+    fn test_try_apply_and_compose() {
+        let key = generate_unimodular_key();
+        let poly: TropicalPolynomial<3> = TropicalPolynomial::from(vec![
+            ([1, 0, 2], TropicalInt::from(5)),
+            ([0, 1, 1], TropicalInt::from(3)),
+        ]);
+
+        assert_eq!(key.try_apply(&poly), Ok(key.apply(&poly)));
+        assert_eq!(
+            key.clone().try_compose(key.clone()),
+            Ok(key.clone().compose(key.clone()))
+        );
+
+        // the inverse has denominators
+        let inverse = key.inverse().unwrap();
+        assert_eq!(inverse.try_apply(&poly), Ok(inverse.apply(&poly)));
+        let rational = TropicalRational::polynomial(poly);
+        assert_eq!(
+            inverse.try_apply_rational(&rational),
+            Ok(inverse.apply_rational(&rational))
+        );
+
+        let huge = TropicalPolynomial::monomial([1, 0, 2], TropicalInt::from(i64::MAX - 1));
+        assert_eq!(key.try_apply(&huge), Err(TropicalError::Overflow));
+
+        let huge_key: TropicalAutomorphism<3> =
+            TropicalAutomorphism::scalar(TropicalInt::from(i64::MAX / 2));
+        assert_eq!(
+            huge_key.clone().try_compose(huge_key.clone()).map(|_| ()),
+            Ok(())
+        );
+        assert_eq!(
+            huge_key
+                .clone()
+                .try_compose(huge_key.clone())
+                .and_then(|square| square.try_compose(huge_key))
+                .map(|_| ()),
+            Err(TropicalError::Overflow)
+        );
+    }
 }
@@ -0,0 +1,307 @@
+/*
+    README: max-plus integers that never overflow. `TropicalInt` adds i64s, so the
+    coefficients of keys with many layers (degrees up to 31 multiply at every composition)
+    wrap around silently. this is slower and not `Copy`, so only use it when that happens.
+*/
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
+use std::iter::Sum;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use crate::semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar};
+use crate::tropical_int::TropicalInt;
+use crate::tropical_polynomial::{Degree, TropicalPolynomial};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TropicalBigInt {
+    AdditiveIdentity,
+    Integer(BigInt),
+}
+
+impl TropicalBigInt {
+    pub fn new(value: BigInt) -> Self {
+        Self::Integer(value)
+    }
+
+    pub fn zero() -> Self {
+        Self::from(0)
+    }
+}
+
+impl Semiring for TropicalBigInt {
+    fn additive_identity() -> Self {
+        Self::AdditiveIdentity
+    }
+
+    fn multiplicative_identity() -> Self {
+        Self::zero()
+    }
+
+    fn pow(&self, power: Degree) -> Self {
+        match self {
+            Self::Integer(int) => Self::Integer(int * BigInt::from(power)),
+            Self::AdditiveIdentity => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl CheckedSemiring for TropicalBigInt {}
+
+impl Semifield for TropicalBigInt {
+    fn inverse(&self) -> Option<Self> {
+        match self {
+            Self::Integer(int) => Some(Self::Integer(-int)),
+            Self::AdditiveIdentity => None,
+        }
+    }
+
+    fn root(&self, index: Degree) -> Option<Self> {
+        if index == 0 {
+            return None;
+        }
+        match self {
+            Self::Integer(int) => {
+                let index = BigInt::from(index);
+                (int % &index == BigInt::ZERO).then(|| Self::Integer(int / index))
+            }
+            Self::AdditiveIdentity => Some(Self::AdditiveIdentity),
+        }
+    }
+}
+
+impl TropicalScalar for TropicalBigInt {
+    fn to_rational(&self) -> Option<BigRational> {
+        match self {
+            Self::Integer(int) => Some(BigRational::from_integer(int.clone())),
+            Self::AdditiveIdentity => None,
+        }
+    }
+
+    fn from_rational_floor(value: &BigRational) -> Self {
+        Self::Integer(value.floor().to_integer())
+    }
+}
+
+impl PartialOrd for TropicalBigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TropicalBigInt {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        match (self, rhs) {
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::AdditiveIdentity, Self::AdditiveIdentity) => Ordering::Equal,
+            (Self::AdditiveIdentity, _) => Ordering::Less,
+            (_, Self::AdditiveIdentity) => Ordering::Greater,
+        }
+    }
+}
+
+impl Add for TropicalBigInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.max(rhs)
+    }
+}
+
+impl AddAssign for TropicalBigInt {
+    fn add_assign(&mut self, rhs: Self) {
+        if rhs > *self {
+            *self = rhs;
+        }
+    }
+}
+
+impl Mul for TropicalBigInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            (Self::Integer(a), Self::Integer(b)) => Self::Integer(a + b),
+            _ => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl MulAssign for TropicalBigInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = core::mem::replace(self, Self::AdditiveIdentity) * rhs;
+    }
+}
+
+impl Div for TropicalBigInt {
+    type Output = TropicalBigInt;
+    fn div(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            (Self::Integer(a), Self::Integer(b)) => Self::Integer(a - b),
+            // README: use `checked_div` to get an error instead
+            (_, Self::AdditiveIdentity) => panic!("div by -inf"),
+            (Self::AdditiveIdentity, _) => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl DivAssign for TropicalBigInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = core::mem::replace(self, Self::AdditiveIdentity) / rhs;
+    }
+}
+
+impl fmt::Display for TropicalBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(a) => write!(f, "{a}"),
+            Self::AdditiveIdentity => write!(f, "-∞"),
+        }
+    }
+}
+
+impl From<i64> for TropicalBigInt {
+    fn from(value: i64) -> Self {
+        TropicalBigInt::new(BigInt::from(value))
+    }
+}
+
+impl From<BigInt> for TropicalBigInt {
+    fn from(value: BigInt) -> Self {
+        TropicalBigInt::new(value)
+    }
+}
+
+impl From<TropicalInt> for TropicalBigInt {
+    fn from(value: TropicalInt) -> Self {
+        match value {
+            TropicalInt::Integer(int) => Self::from(int),
+            TropicalInt::AdditiveIdentity => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl Sum for TropicalBigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(TropicalBigInt::AdditiveIdentity, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> From<TropicalPolynomial<N, TropicalInt>>
+    for TropicalPolynomial<N, TropicalBigInt>
+{
+    fn from(poly: TropicalPolynomial<N, TropicalInt>) -> Self {
+        poly.map_coefficients(|c| TropicalBigInt::from(*c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use super::TropicalBigInt;
+    use crate::{
+        error::TropicalError,
+        semiring::{CheckedSemiring, Semifield, Semiring},
+        tropical_automorphism::TropicalAutomorphism,
+        tropical_int::TropicalInt,
+        tropical_polynomial::TropicalPolynomial,
+    };
+
+    #[test]
+    fn test_tropical_big_int() {
+        let big = TropicalBigInt::from(BigInt::from(i64::MAX) * 4);
+        let test_table: Vec<[TropicalBigInt; 4]> = vec![
+            // a, b, a + b, a * b
+            [
+                TropicalBigInt::from(3),
+                TropicalBigInt::from(5),
+                TropicalBigInt::from(5),
+                TropicalBigInt::from(8),
+            ],
+            [
+                TropicalBigInt::from(i64::MAX),
+                TropicalBigInt::from(i64::MAX),
+                TropicalBigInt::from(i64::MAX),
+                TropicalBigInt::from(BigInt::from(i64::MAX) * 2),
+            ],
+            [
+                big.clone(),
+                TropicalBigInt::AdditiveIdentity,
+                big,
+                TropicalBigInt::AdditiveIdentity,
+            ],
+        ];
+
+        for [a, b, sum, product] in test_table {
+            assert_eq!(a.clone() + b.clone(), sum);
+            assert_eq!(a * b, product);
+        }
+
+        assert_eq!(
+            TropicalBigInt::from(i64::MAX).pow(3),
+            TropicalBigInt::from(BigInt::from(i64::MAX) * 3)
+        );
+        assert_eq!(
+            TropicalBigInt::from(6).root(3),
+            Some(TropicalBigInt::from(2))
+        );
+        assert_eq!(TropicalBigInt::from(6).root(0), None);
+        assert_eq!(
+            TropicalBigInt::from(3).checked_div(&TropicalBigInt::from(5)),
+            Ok(TropicalBigInt::from(-2))
+        );
+        assert_eq!(
+            TropicalBigInt::from(3).checked_div(&TropicalBigInt::AdditiveIdentity),
+            Err(TropicalError::DivisionByAdditiveIdentity)
+        );
+    }
+
+    #[test]
+    fn test_big_int_automorphism() {
+        // x -> x^2 y, y -> x y three times, the values at i64::MAX get 21 times bigger
+        let key: TropicalAutomorphism<2, TropicalBigInt> =
+            TropicalAutomorphism::monomial([[2, 1], [1, 1]], [0, 0].map(TropicalBigInt::from));
+        let key = key.clone().compose(key.clone()).compose(key);
+        let poly: TropicalPolynomial<2, TropicalBigInt> = TropicalPolynomial::from(
+            TropicalPolynomial::<2>::monomial([1, 0], TropicalInt::from(1 << 62)),
+        );
+
+        let image = key.apply(&poly).numerator;
+        assert_eq!(
+            image.get_term(&[13, 8]),
+            Some(&TropicalBigInt::from(1 << 62))
+        );
+
+        let point = [
+            TropicalBigInt::from(i64::MAX),
+            TropicalBigInt::from(i64::MAX),
+        ];
+        let value = key.evaluate(point.clone()).unwrap();
+        assert_eq!(value[0], TropicalBigInt::from(BigInt::from(i64::MAX) * 21));
+        assert_eq!(key.invert_at(value), Ok(point));
+    }
+
+    #[cfg(feature = "wide-degrees")]
+    #[test]
+    fn test_wide_degrees() {
+        let poly: TropicalPolynomial<2, TropicalBigInt> = TropicalPolynomial::from(vec![
+            ([1 << 70, 0], TropicalBigInt::from(3)),
+            ([0, 1], TropicalBigInt::from(0)),
+        ]);
+
+        let square = poly.clone() * poly;
+        assert_eq!(
+            square.get_term(&[1 << 71, 0]),
+            Some(&TropicalBigInt::from(6))
+        );
+        assert_eq!(
+            square.get_term(&[1 << 70, 1]),
+            Some(&TropicalBigInt::from(3))
+        );
+    }
+}
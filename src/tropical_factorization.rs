@@ -0,0 +1,482 @@
+/*
+    README: heuristics from "Linear and Rational Factorization of Tropical Polynomials".
+    factoring a tropical polynomial is factoring its lifted newton polytope as a minkowski sum,
+    which is NP-hard in general, so we only look for the summands that are cheap to guess:
+    the monomial content, k-th roots (f = g^k), segments (binomials) along the edges
+    between vertices and linear polynomials a_0 + a_1 x_1 + ... (simplices) whose edges
+    all show up in the newton polytope. when none of those divide we look for a rational
+    factorization f = (g / h) q, i.e. a summand g that only fits once we add the summand h.
+    the coefficients are worked out as rationals, candidates that need a coefficient the
+    scalar can't hold (like 3/2 for integers) are skipped.
+*/
+use core::fmt;
+
+use num_rational::BigRational;
+use num_traits::Zero;
+
+use crate::{
+    linear_program::rational,
+    semiring::TropicalScalar,
+    tropical_int::TropicalInt,
+    tropical_polynomial::{gcd, Degree, TropicalPolynomial},
+    tropical_rational::TropicalRational,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Factorization<const N: usize, T = TropicalInt> {
+    /// None of the heuristics found a factor, which doesn't prove irreducibility in general.
+    Irreducible(TropicalPolynomial<N, T>),
+    Polynomial(Vec<TropicalPolynomial<N, T>>),
+    Rational(Vec<TropicalRational<N, T>>),
+}
+
+impl<const N: usize, T: TropicalScalar> Factorization<N, T> {
+    pub fn is_irreducible(&self) -> bool {
+        matches!(self, Self::Irreducible(_))
+    }
+
+    pub fn expand(&self) -> TropicalRational<N, T> {
+        match self {
+            Self::Irreducible(poly) => TropicalRational::polynomial(poly.clone()),
+            Self::Polynomial(factors) => TropicalRational::polynomial(factors.iter().fold(
+                TropicalPolynomial::multiplicative_identity(),
+                |acc, factor| acc * factor.clone(),
+            )),
+            Self::Rational(factors) => factors.iter().fold(
+                TropicalRational::polynomial(TropicalPolynomial::multiplicative_identity()),
+                |acc, factor| acc * factor.clone(),
+            ),
+        }
+    }
+}
+
+impl<const N: usize, T: TropicalScalar> TropicalPolynomial<N, T> {
+    /// The factorization entry point for any number of variables, using the heuristics above.
+    /// With one variable `factor_linear` gives the complete factorization into linear factors.
+    pub fn factor(&self) -> Factorization<N, T> {
+        let poly = self.normal_form();
+        if poly.terms.len() <= 1 {
+            return Factorization::Irreducible(poly);
+        }
+
+        let mut factors = vec![];
+
+        let content: [Degree; N] =
+            core::array::from_fn(|i| poly.terms.keys().map(|d| d[i]).min().unwrap());
+        let mut remaining = if content.iter().any(|d| *d != 0) {
+            factors.push(TropicalPolynomial::monomial(
+                content,
+                T::multiplicative_identity(),
+            ));
+            shift(&poly, content)
+        } else {
+            poly.clone()
+        };
+
+        while let Some((factor, quotient)) = find_divisor(&remaining) {
+            factors.push(factor);
+            remaining = quotient;
+        }
+
+        if factors.is_empty() {
+            return match find_rational_factor(&remaining) {
+                Some(rational_factors) => Factorization::Rational(rational_factors),
+                None => Factorization::Irreducible(poly),
+            };
+        }
+
+        if remaining != TropicalPolynomial::multiplicative_identity() {
+            factors.push(remaining);
+        }
+
+        Factorization::Polynomial(factors)
+    }
+}
+
+fn shift<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+    content: [Degree; N],
+) -> TropicalPolynomial<N, T> {
+    TropicalPolynomial::from(
+        poly.terms
+            .iter()
+            .map(|(multi_degree, coefficient)| {
+                (
+                    core::array::from_fn(|i| multi_degree[i] - content[i]),
+                    coefficient.clone(),
+                )
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+// README: `value` as a scalar, if the scalar can hold it exactly
+fn exact<T: TropicalScalar>(value: &BigRational) -> Option<T> {
+    let scalar = T::from_rational_floor(value);
+    (scalar.to_rational().as_ref() == Some(value)).then_some(scalar)
+}
+
+/*
+    README: for every pair of vertices (d, c) and (d', c') with d - d' = k u, u primitive, the
+    segments j u with coefficient j (c - c') / k are the binomial summands that could fit along
+    that direction, we try the shortest ones first so the factors we find are irreducible.
+*/
+fn binomial_candidates<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+) -> Vec<TropicalPolynomial<N, T>> {
+    let terms = poly.lifted_terms();
+    let mut candidates: Vec<(Degree, [Degree; N], BigRational)> = vec![];
+
+    for (a, (degree_a, coefficient_a)) in terms.iter().enumerate() {
+        for (degree_b, coefficient_b) in terms.iter().skip(a + 1) {
+            let direction: [Degree; N] = core::array::from_fn(|i| degree_a[i] - degree_b[i]);
+            let k = direction.iter().fold(0, |acc, d| gcd(acc, *d));
+            let difference = coefficient_a - coefficient_b;
+
+            for j in 1..=k {
+                let mut segment: [Degree; N] = core::array::from_fn(|i| j * direction[i] / k);
+                let mut coefficient = &difference * rational(j) / rational(k);
+                // README: orient the segment so both directions give the same candidate
+                if segment.iter().find(|d| **d != 0).is_some_and(|d| *d < 0) {
+                    segment = segment.map(|d| -d);
+                    coefficient = -coefficient;
+                }
+                candidates.push((j, segment, coefficient));
+            }
+        }
+    }
+
+    candidates.sort();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .filter_map(|(_, segment, coefficient)| {
+            let zero = BigRational::zero();
+            Some(TropicalPolynomial::from(vec![
+                (
+                    segment.map(|d| d.max(0)),
+                    exact(&coefficient.clone().max(zero.clone()))?,
+                ),
+                (
+                    segment.map(|d| (-d).max(0)),
+                    exact(&(-coefficient).max(zero))?,
+                ),
+            ]))
+        })
+        .collect()
+}
+
+fn unit<const N: usize>(index: usize) -> [Degree; N] {
+    core::array::from_fn(|i| Degree::from(i == index))
+}
+
+/*
+    README: a linear factor sum_{i in S} a_i x_i (x_N standing for the constant) has the edges
+    e_i - e_j with slopes a_i - a_j, so we collect those slopes from the vertex pairs and try
+    every subset S with at least three members whose slopes are consistent.
+*/
+fn linear_candidates<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+) -> Vec<TropicalPolynomial<N, T>> {
+    let terms = poly.lifted_terms();
+    let mut slopes: Vec<Vec<Vec<BigRational>>> = vec![vec![vec![]; N + 1]; N + 1];
+
+    for (a, (degree_a, coefficient_a)) in terms.iter().enumerate() {
+        for (degree_b, coefficient_b) in terms.iter().skip(a + 1) {
+            let direction: [Degree; N] = core::array::from_fn(|i| degree_a[i] - degree_b[i]);
+            let positive: Vec<usize> = (0..N).filter(|&i| direction[i] > 0).collect();
+            let negative: Vec<usize> = (0..N).filter(|&i| direction[i] < 0).collect();
+
+            let (i, j, k) = match (positive.as_slice(), negative.as_slice()) {
+                ([i], [j]) if direction[*i] == -direction[*j] => (*i, *j, direction[*i]),
+                ([i], []) => (*i, N, direction[*i]),
+                ([], [j]) => (N, *j, -direction[*j]),
+                _ => continue,
+            };
+            let slope = (coefficient_a - coefficient_b) / rational(k);
+
+            slopes[j][i].push(-slope.clone());
+            slopes[i][j].push(slope);
+        }
+    }
+    for row in slopes.iter_mut() {
+        for options in row.iter_mut() {
+            options.sort();
+            options.dedup();
+        }
+    }
+
+    let mut candidates = vec![];
+    for subset in 1usize..(1 << (N + 1)) {
+        if subset.count_ones() < 3 {
+            continue;
+        }
+        let members: Vec<usize> = (0..=N).filter(|i| subset & (1 << i) != 0).collect();
+        let reference = members[0];
+
+        // every assignment of a_i - a_reference, filtered by the slopes between the others
+        let mut assignments: Vec<Vec<BigRational>> = vec![vec![BigRational::zero()]];
+        for (position, &member) in members.iter().enumerate().skip(1) {
+            let mut extended = vec![];
+            for assignment in &assignments {
+                for slope in &slopes[member][reference] {
+                    let consistent = (1..position).all(|other| {
+                        slopes[member][members[other]].contains(&(slope - &assignment[other]))
+                    });
+                    if consistent {
+                        extended.push([assignment.clone(), vec![slope.clone()]].concat());
+                    }
+                }
+            }
+            assignments = extended;
+        }
+
+        candidates.extend(assignments.into_iter().filter_map(|assignment| {
+            Some(TropicalPolynomial::from(
+                members
+                    .iter()
+                    .zip(assignment)
+                    .map(|(&member, coefficient)| Some((unit(member), exact(&coefficient)?)))
+                    .collect::<Option<Vec<_>>>()?,
+            ))
+        }));
+    }
+
+    candidates
+}
+
+fn candidates<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+) -> Vec<TropicalPolynomial<N, T>> {
+    let mut candidates = binomial_candidates(poly);
+    candidates.extend(linear_candidates(poly));
+    candidates
+}
+
+/*
+    README: Frobenius, if every degree is a multiple of k and the coefficients differ by
+    multiples of k then f = c g^k. we try the divisors of the gcd of the degrees from the
+    largest down
+*/
+fn root_candidate<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+) -> Option<TropicalPolynomial<N, T>> {
+    let terms = poly.lifted_terms();
+    let (_, reference) = terms.first()?.clone();
+    let degrees = terms.iter().fold(0, |acc, (multi_degree, _)| {
+        multi_degree.iter().fold(acc, |acc, d| gcd(acc, *d))
+    });
+
+    (2..=degrees)
+        .rev()
+        .filter(|k| degrees % k == 0)
+        .find_map(|k| {
+            Some(TropicalPolynomial::from(
+                terms
+                    .iter()
+                    .map(|(multi_degree, coefficient)| {
+                        Some((
+                            multi_degree.map(|d| d / k),
+                            exact(&((coefficient - &reference) / rational(k)))?,
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+            ))
+        })
+}
+
+fn is_proper_factor<const N: usize, T: TropicalScalar>(
+    factor: &TropicalPolynomial<N, T>,
+    quotient: &TropicalPolynomial<N, T>,
+) -> bool {
+    factor.terms.len() > 1 && quotient.normal_form().terms.len() > 1
+}
+
+fn find_divisor<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+) -> Option<(TropicalPolynomial<N, T>, TropicalPolynomial<N, T>)> {
+    if poly.terms.len() <= 1 {
+        return None;
+    }
+
+    root_candidate(poly)
+        .into_iter()
+        .chain(candidates(poly))
+        .find_map(|candidate| {
+            let quotient = poly.exact_quotient(&candidate)?;
+            is_proper_factor(&candidate, &quotient).then_some((candidate, quotient.normal_form()))
+        })
+}
+
+fn find_rational_factor<const N: usize, T: TropicalScalar>(
+    poly: &TropicalPolynomial<N, T>,
+) -> Option<Vec<TropicalRational<N, T>>> {
+    candidates(poly).into_iter().find_map(|denominator| {
+        let product = (poly.clone() * denominator.clone()).normal_form();
+        candidates(&product)
+            .into_iter()
+            .filter(|numerator| {
+                !numerator.is_divisible_by(&denominator) && !denominator.is_divisible_by(numerator)
+            })
+            .find_map(|numerator| {
+                let quotient = product.exact_quotient(&numerator)?;
+                (is_proper_factor(&numerator, &quotient) && !quotient.is_divisible_by(&denominator))
+                    .then(|| {
+                        vec![
+                            TropicalRational::new(numerator, denominator.clone()),
+                            TropicalRational::polynomial(quotient.normal_form()),
+                        ]
+                    })
+            })
+    })
+}
+
+impl<const N: usize, T: fmt::Display> fmt::Display for Factorization<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Irreducible(poly) => write!(f, "irreducible {poly}"),
+            Self::Polynomial(factors) => {
+                for (i, factor) in factors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " * ")?;
+                    }
+                    write!(f, "({factor})")?;
+                }
+                Ok(())
+            }
+            Self::Rational(factors) => {
+                for (i, factor) in factors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " * ")?;
+                    }
+                    write!(f, "{factor}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tropical_fraction::{fraction, TropicalFraction},
+        tropical_int::TropicalInt,
+        tropical_polynomial::TropicalPolynomial,
+        tropical_rational::TropicalRational,
+    };
+
+    use super::Factorization;
+
+    #[test]
+    fn test_factor_polynomial() {
+        let x = TropicalPolynomial::<3>::variable(0);
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+        let constant = |c: i64| TropicalPolynomial::<3>::constant(TropicalInt::from(c));
+
+        let test_table: Vec<(TropicalPolynomial<3>, usize)> = vec![
+            // xz(x + 1)(y + 2)
+            (
+                x.clone() * z.clone() * (x.clone() + constant(1)) * (y.clone() + constant(2)),
+                3,
+            ),
+            // (x + y + z)², found as a square
+            ((x.clone() + y.clone() + z.clone()).pow(2), 2),
+            // (x + y + 0)(y + 3z)
+            (
+                (x.clone() + y.clone() + constant(0)) * (y.clone() + constant(3) * z.clone()),
+                2,
+            ),
+            // (x + y + 0)(x + 1y + 3), no binomial divides this one
+            (
+                (x.clone() + y.clone() + constant(0))
+                    * (x.clone() + constant(1) * y.clone() + constant(3)),
+                2,
+            ),
+            // (x + y + z)(x + 2)(y + 1z)
+            (
+                (x.clone() + y.clone() + z.clone())
+                    * (x.clone() + constant(2))
+                    * (y.clone() + constant(1) * z.clone()),
+                3,
+            ),
+        ];
+
+        for (poly, factors) in test_table {
+            let factorization = poly.factor();
+            match &factorization {
+                Factorization::Polynomial(found) => assert_eq!(found.len(), factors),
+                other => panic!("expected a polynomial factorization, got {other}"),
+            }
+            assert!(factorization
+                .expand()
+                .functionally_eq(&TropicalRational::polynomial(poly)));
+        }
+    }
+
+    #[test]
+    fn test_factor_irreducible() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+        let constant = |c: i64| TropicalPolynomial::<2>::constant(TropicalInt::from(c));
+
+        let test_table: Vec<TropicalPolynomial<2>> = vec![
+            // the newton polytope is a triangle
+            x.clone() + y.clone() + constant(0),
+            x.clone() * y.clone() + x.pow(2) + constant(1),
+            // x² + 3 would need the root 3/2 which isn't an integer
+            x.pow(2) + constant(3),
+        ];
+
+        for poly in test_table {
+            assert!(poly.factor().is_irreducible(), "{poly}");
+        }
+    }
+
+    #[test]
+    fn test_factor_fractions() {
+        let x = TropicalPolynomial::<2, TropicalFraction>::variable(0);
+        let y = TropicalPolynomial::<2, TropicalFraction>::variable(1);
+        let constant = |a: i64, b: i64| TropicalPolynomial::<2, _>::constant(fraction(a, b));
+
+        let test_table: Vec<TropicalPolynomial<2, TropicalFraction>> = vec![
+            // (x + 3/2)², the integers can't factor x² + 3
+            x.pow(2) + constant(3, 1),
+            // (x + 1/2)(y + 1/3)
+            (x.clone() + constant(1, 2)) * (y.clone() + constant(1, 3)),
+        ];
+
+        for poly in test_table {
+            let factorization = poly.factor();
+            match &factorization {
+                Factorization::Polynomial(found) => assert_eq!(found.len(), 2),
+                other => panic!("expected a polynomial factorization, got {other}"),
+            }
+            assert!(factorization
+                .expand()
+                .functionally_eq(&TropicalRational::polynomial(poly)));
+        }
+    }
+
+    #[test]
+    fn test_factor_rational() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+        let zero = TropicalPolynomial::<2>::multiplicative_identity();
+
+        // xy + x + y is a triangle, but (xy + x + y)(x + y + 0) = (x + 0)(y + 0)(x + y)
+        let poly = x.clone() * y.clone() + x.clone() + y.clone();
+        let factorization = poly.factor();
+
+        assert!(matches!(factorization, Factorization::Rational(_)));
+        assert!(factorization
+            .expand()
+            .functionally_eq(&TropicalRational::polynomial(poly)));
+        assert!(TropicalRational::new(
+            (x.clone() + zero.clone()) * (y.clone() + zero.clone()) * (x.clone() + y.clone()),
+            x + y + zero,
+        )
+        .functionally_eq(&factorization.expand()));
+    }
+}
@@ -0,0 +1,366 @@
+/*
+    README: max-plus floats for experiments with real coefficients and points (tropical
+    neural networks, the fractional points of radical inverses). f64::NEG_INFINITY is the
+    additive identity, NaN and +∞ are rejected when building one so the order is total
+    and ⊙ never sees -∞ + ∞. a product that overflows to +∞ is an error in `checked_mul`
+    and saturates to the largest float otherwise. -∞ to a negative power and division by
+    -∞ would be +∞, `checked_pow` and `checked_div` give errors and the operators panic. -0.0 is stored as 0.0, total_cmp would
+    put it right below 0.0 and `==` goes through total_cmp too.
+*/
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
+use std::iter::Sum;
+
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use crate::error::TropicalError;
+use crate::semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar};
+use crate::tropical_int::TropicalInt;
+use crate::tropical_polynomial::{Degree, TropicalPolynomial};
+
+#[derive(Copy, Clone, Debug)]
+pub struct TropicalFloat(f64);
+
+impl TropicalFloat {
+    pub fn new(value: f64) -> Result<Self, TropicalError> {
+        if value == f64::INFINITY {
+            return Err(TropicalError::NotANumber);
+        }
+        Self::checked(value)
+    }
+
+    // README: every float is built through here
+    fn checked(value: f64) -> Result<Self, TropicalError> {
+        if value.is_nan() {
+            return Err(TropicalError::NotANumber);
+        }
+        Ok(Self(if value == 0.0 { 0.0 } else { value }))
+    }
+
+    pub fn zero() -> Self {
+        Self(0.0)
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    // README: the only way to get +∞ from valid floats is overflowing, we stay finite
+    fn saturating(value: f64) -> Self {
+        let Self(value) = Self::checked(value).expect("valid floats never give NaN");
+        Self(value.min(f64::MAX))
+    }
+}
+
+impl Semiring for TropicalFloat {
+    fn additive_identity() -> Self {
+        Self(f64::NEG_INFINITY)
+    }
+
+    fn multiplicative_identity() -> Self {
+        Self::zero()
+    }
+
+    fn is_additive_identity(&self) -> bool {
+        self.0 == f64::NEG_INFINITY
+    }
+
+    fn pow(&self, power: Degree) -> Self {
+        if power == 0 {
+            return Self::zero();
+        }
+        match self.is_additive_identity() {
+            // README: use `checked_pow` to get an error instead
+            true if power < 0 => panic!("-inf to a negative power"),
+            true => *self,
+            false => Self::saturating(self.0 * power as f64),
+        }
+    }
+}
+
+impl CheckedSemiring for TropicalFloat {
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        if self.0 + rhs.0 == f64::INFINITY {
+            return Err(TropicalError::Overflow);
+        }
+        Ok(*self * *rhs)
+    }
+
+    fn checked_pow(&self, power: Degree) -> Result<Self, TropicalError> {
+        if self.is_additive_identity() && power < 0 {
+            return Err(TropicalError::DivisionByAdditiveIdentity);
+        }
+        if !self.is_additive_identity() && (self.0 * power as f64).is_infinite() {
+            return Err(TropicalError::Overflow);
+        }
+        Ok(self.pow(power))
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        if rhs.is_additive_identity() {
+            return Err(TropicalError::DivisionByAdditiveIdentity);
+        }
+        if self.0 - rhs.0 == f64::INFINITY {
+            return Err(TropicalError::Overflow);
+        }
+        Ok(*self / *rhs)
+    }
+}
+
+impl Semifield for TropicalFloat {
+    fn inverse(&self) -> Option<Self> {
+        (!self.is_additive_identity()).then(|| Self::saturating(-self.0))
+    }
+
+    fn root(&self, index: Degree) -> Option<Self> {
+        if index == 0 {
+            return None;
+        }
+        Self::new(self.0 / index as f64).ok()
+    }
+}
+
+impl TropicalScalar for TropicalFloat {
+    fn to_rational(&self) -> Option<BigRational> {
+        BigRational::from_float(self.0)
+    }
+
+    fn from_rational_floor(value: &BigRational) -> Self {
+        let float = value
+            .to_f64()
+            .expect("rationals always have a float approximation");
+        if BigRational::from_float(float).is_some_and(|f| f > *value) {
+            Self::saturating(float.next_down())
+        } else {
+            Self::saturating(float)
+        }
+    }
+}
+
+impl PartialEq for TropicalFloat {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.cmp(rhs) == Ordering::Equal
+    }
+}
+
+impl Eq for TropicalFloat {}
+
+impl PartialOrd for TropicalFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TropicalFloat {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.total_cmp(&rhs.0)
+    }
+}
+
+impl Add for TropicalFloat {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.max(rhs)
+    }
+}
+
+impl AddAssign for TropicalFloat {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul for TropicalFloat {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        Self::saturating(self.0 + rhs.0)
+    }
+}
+
+impl MulAssign for TropicalFloat {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for TropicalFloat {
+    type Output = TropicalFloat;
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.is_additive_identity() {
+            // README: use `checked_div` to get an error instead
+            panic!("div by -inf");
+        }
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        Self::saturating(self.0 - rhs.0)
+    }
+}
+
+impl DivAssign for TropicalFloat {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl fmt::Display for TropicalFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_additive_identity() {
+            write!(f, "-∞")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl TryFrom<f64> for TropicalFloat {
+    type Error = TropicalError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        TropicalFloat::new(value)
+    }
+}
+
+impl From<TropicalInt> for TropicalFloat {
+    /// Integers past 2^53 get rounded.
+    fn from(value: TropicalInt) -> Self {
+        match value {
+            TropicalInt::Integer(int) => Self(int as f64),
+            TropicalInt::AdditiveIdentity => Self::additive_identity(),
+        }
+    }
+}
+
+impl Sum for TropicalFloat {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(TropicalFloat::additive_identity(), |a, b| a + b)
+    }
+}
+
+impl<const N: usize> From<TropicalPolynomial<N, TropicalInt>>
+    for TropicalPolynomial<N, TropicalFloat>
+{
+    fn from(poly: TropicalPolynomial<N, TropicalInt>) -> Self {
+        poly.map_coefficients(|c| TropicalFloat::from(*c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use super::TropicalFloat;
+    use crate::{
+        error::TropicalError,
+        linear_program::rational,
+        semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar},
+        tropical_automorphism::TropicalAutomorphism,
+        tropical_int::TropicalInt,
+        tropical_polynomial::TropicalPolynomial,
+    };
+
+    // README: numerator / denominator rounded down
+    fn float(numerator: i64, denominator: i64) -> TropicalFloat {
+        TropicalFloat::from_rational_floor(&(rational(numerator) / rational(denominator)))
+    }
+
+    #[test]
+    fn test_tropical_float() {
+        let test_table: Vec<[TropicalFloat; 4]> = vec![
+            // a, b, a + b, a * b
+            [float(1, 2), float(-3, 4), float(1, 2), float(-1, 4)],
+            [
+                float(3, 1),
+                TropicalFloat::additive_identity(),
+                float(3, 1),
+                TropicalFloat::additive_identity(),
+            ],
+        ];
+
+        for [a, b, sum, product] in test_table {
+            assert_eq!(a + b, sum);
+            assert_eq!(a * b, product);
+        }
+
+        assert_eq!(TropicalFloat::new(f64::NAN), Err(TropicalError::NotANumber));
+        assert_eq!(
+            TropicalFloat::try_from(f64::INFINITY),
+            Err(TropicalError::NotANumber)
+        );
+        assert!(TropicalFloat::new(f64::NEG_INFINITY)
+            .unwrap()
+            .is_additive_identity());
+        // 1/3 isn't a float, the floor is the one right below it
+        assert!(float(1, 3).to_rational().unwrap() < rational(1) / rational(3));
+
+        // there's only one zero, so == and total_cmp agree
+        let negative_zero = TropicalFloat::new(-0.0).unwrap();
+        assert_eq!(negative_zero, TropicalFloat::zero());
+        assert_eq!(negative_zero.cmp(&TropicalFloat::zero()), Ordering::Equal);
+        assert_eq!(TropicalFloat::zero().inverse(), Some(TropicalFloat::zero()));
+        assert_eq!(float(-1, 2) * float(1, 2), TropicalFloat::zero());
+        assert_eq!(float(1, 2) / float(1, 2), TropicalFloat::zero());
+
+        // roots that would be NaN or +∞
+        assert_eq!(float(1, 2).root(2), Some(float(1, 4)));
+        assert_eq!(float(1, 2).root(0), None);
+        assert_eq!(TropicalFloat::zero().root(0), None);
+        assert_eq!(TropicalFloat::additive_identity().root(-1), None);
+        assert_eq!(
+            TropicalFloat::additive_identity().root(2),
+            Some(TropicalFloat::additive_identity())
+        );
+    }
+
+    #[test]
+    fn test_checked() {
+        let max = TropicalFloat::new(f64::MAX).unwrap();
+        let bottom = TropicalFloat::additive_identity();
+
+        assert_eq!(float(1, 2).checked_pow(-2), Ok(float(-1, 1)));
+        assert_eq!(bottom.checked_pow(3), Ok(bottom));
+        assert_eq!(
+            bottom.checked_pow(-1),
+            Err(TropicalError::DivisionByAdditiveIdentity)
+        );
+        assert_eq!(max.checked_pow(2), Err(TropicalError::Overflow));
+
+        assert_eq!(float(1, 2).checked_div(&float(3, 1)), Ok(float(-5, 2)));
+        assert_eq!(bottom.checked_div(&float(3, 1)), Ok(bottom));
+        assert_eq!(
+            float(1, 2).checked_div(&bottom),
+            Err(TropicalError::DivisionByAdditiveIdentity)
+        );
+        assert_eq!(
+            max.checked_div(&max.inverse().unwrap()),
+            Err(TropicalError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_at_real_points() {
+        // a = (3x²y, 4xy²) from the docs, a⁻¹(s) has fractional coordinates
+        let key: TropicalAutomorphism<2, TropicalFloat> = TropicalAutomorphism::monomial(
+            [[2, 1], [1, 2]],
+            [TropicalInt::from(3), TropicalInt::from(4)].map(TropicalFloat::from),
+        );
+        let poly = TropicalPolynomial::<2, TropicalFloat>::from(TropicalPolynomial::from(vec![
+            ([2, 0], TropicalInt::from(1)),
+            ([0, 1], TropicalInt::from(-2)),
+        ]));
+
+        let point = [TropicalFloat::zero(), TropicalFloat::zero()];
+        let preimage = key.evaluate_inverse(point).unwrap();
+        assert!((preimage[0].value() - -2.0 / 3.0).abs() < 1e-12);
+        assert!((preimage[1].value() - -5.0 / 3.0).abs() < 1e-12);
+
+        // 1x² + -2y at (-2/3, -5/3) is max(1 - 4/3, -2 - 5/3) = -1/3
+        assert!((poly.evaluate(preimage).value() - -1.0 / 3.0).abs() < 1e-12);
+        for (value, expected) in key.evaluate(preimage).unwrap().iter().zip(point) {
+            assert!((value.value() - expected.value()).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+/*
+    README: max-plus rationals. the inverse of a monomial automorphism with det(M) != ±1 has
+    fractional degrees, (s² / 2z)^(1/3) for the 2x2 example in the docs, so its values are
+    only integers at some points. over these every root exists and nothing gets lost.
+*/
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
+use std::iter::Sum;
+
+use num_rational::BigRational;
+
+use crate::linear_program::rational;
+use crate::semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar};
+use crate::tropical_int::TropicalInt;
+use crate::tropical_polynomial::{Degree, TropicalPolynomial};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TropicalFraction {
+    AdditiveIdentity,
+    Fraction(BigRational),
+}
+
+impl TropicalFraction {
+    pub fn new(value: BigRational) -> Self {
+        Self::Fraction(value)
+    }
+
+    pub fn zero() -> Self {
+        Self::from(0)
+    }
+}
+
+impl Semiring for TropicalFraction {
+    fn additive_identity() -> Self {
+        Self::AdditiveIdentity
+    }
+
+    fn multiplicative_identity() -> Self {
+        Self::zero()
+    }
+
+    fn pow(&self, power: Degree) -> Self {
+        match self {
+            Self::Fraction(value) => Self::Fraction(value * rational(power)),
+            Self::AdditiveIdentity => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl CheckedSemiring for TropicalFraction {}
+
+impl Semifield for TropicalFraction {
+    fn inverse(&self) -> Option<Self> {
+        match self {
+            Self::Fraction(value) => Some(Self::Fraction(-value)),
+            Self::AdditiveIdentity => None,
+        }
+    }
+
+    fn root(&self, index: Degree) -> Option<Self> {
+        if index == 0 {
+            return None;
+        }
+        match self {
+            Self::Fraction(value) => Some(Self::Fraction(value / rational(index))),
+            Self::AdditiveIdentity => Some(Self::AdditiveIdentity),
+        }
+    }
+}
+
+impl TropicalScalar for TropicalFraction {
+    fn to_rational(&self) -> Option<BigRational> {
+        match self {
+            Self::Fraction(value) => Some(value.clone()),
+            Self::AdditiveIdentity => None,
+        }
+    }
+
+    fn from_rational_floor(value: &BigRational) -> Self {
+        Self::Fraction(value.clone())
+    }
+}
+
+impl PartialOrd for TropicalFraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TropicalFraction {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        match (self, rhs) {
+            (Self::Fraction(a), Self::Fraction(b)) => a.cmp(b),
+            (Self::AdditiveIdentity, Self::AdditiveIdentity) => Ordering::Equal,
+            (Self::AdditiveIdentity, _) => Ordering::Less,
+            (_, Self::AdditiveIdentity) => Ordering::Greater,
+        }
+    }
+}
+
+impl Add for TropicalFraction {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.max(rhs)
+    }
+}
+
+impl AddAssign for TropicalFraction {
+    fn add_assign(&mut self, rhs: Self) {
+        if rhs > *self {
+            *self = rhs;
+        }
+    }
+}
+
+impl Mul for TropicalFraction {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            (Self::Fraction(a), Self::Fraction(b)) => Self::Fraction(a + b),
+            _ => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl MulAssign for TropicalFraction {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = core::mem::replace(self, Self::AdditiveIdentity) * rhs;
+    }
+}
+
+impl Div for TropicalFraction {
+    type Output = TropicalFraction;
+    fn div(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            (Self::Fraction(a), Self::Fraction(b)) => Self::Fraction(a - b),
+            // README: use `checked_div` to get an error instead
+            (_, Self::AdditiveIdentity) => panic!("div by -inf"),
+            (Self::AdditiveIdentity, _) => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl DivAssign for TropicalFraction {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = core::mem::replace(self, Self::AdditiveIdentity) / rhs;
+    }
+}
+
+impl fmt::Display for TropicalFraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Fraction(a) => write!(f, "{a}"),
+            Self::AdditiveIdentity => write!(f, "-∞"),
+        }
+    }
+}
+
+impl From<i64> for TropicalFraction {
+    fn from(value: i64) -> Self {
+        TropicalFraction::new(rational(value))
+    }
+}
+
+impl From<BigRational> for TropicalFraction {
+    fn from(value: BigRational) -> Self {
+        TropicalFraction::new(value)
+    }
+}
+
+impl From<TropicalInt> for TropicalFraction {
+    fn from(value: TropicalInt) -> Self {
+        match value {
+            TropicalInt::Integer(int) => Self::from(int),
+            TropicalInt::AdditiveIdentity => Self::AdditiveIdentity,
+        }
+    }
+}
+
+impl Sum for TropicalFraction {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(TropicalFraction::AdditiveIdentity, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> From<TropicalPolynomial<N, TropicalInt>>
+    for TropicalPolynomial<N, TropicalFraction>
+{
+    fn from(poly: TropicalPolynomial<N, TropicalInt>) -> Self {
+        poly.map_coefficients(|c| TropicalFraction::from(*c))
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn fraction(numerator: i64, denominator: i64) -> TropicalFraction {
+    TropicalFraction::new(rational(numerator) / rational(denominator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fraction, TropicalFraction};
+    use crate::{
+        linear_program::rational,
+        semiring::{Semifield, Semiring, TropicalScalar},
+    };
+
+    #[test]
+    fn test_tropical_fraction() {
+        let test_table: Vec<[TropicalFraction; 4]> = vec![
+            // a, b, a + b, a * b
+            [
+                fraction(1, 2),
+                fraction(1, 3),
+                fraction(1, 2),
+                fraction(5, 6),
+            ],
+            [
+                fraction(-7, 3),
+                TropicalFraction::AdditiveIdentity,
+                fraction(-7, 3),
+                TropicalFraction::AdditiveIdentity,
+            ],
+        ];
+
+        for [a, b, sum, product] in test_table {
+            assert_eq!(a.clone() + b.clone(), sum);
+            assert_eq!(a * b, product);
+        }
+
+        assert_eq!(TropicalFraction::from(2).root(3), Some(fraction(2, 3)));
+        assert_eq!(TropicalFraction::from(2).root(0), None);
+        assert_eq!(fraction(2, 3).pow(3), TropicalFraction::from(2));
+        assert_eq!(
+            TropicalFraction::from_rational_floor(&(rational(7) / rational(2))),
+            fraction(7, 2)
+        );
+    }
+}
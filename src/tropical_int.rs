@@ -3,6 +3,14 @@ use core::fmt;
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
 use std::iter::Sum;
 
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use crate::error::TropicalError;
+use crate::linear_program::rational;
+use crate::semiring::{CheckedSemiring, Semifield, Semiring, TropicalScalar};
+use crate::tropical_polynomial::{checked_narrow_degree, narrow_degree, Degree};
+
 #[derive(Copy, Clone, Debug)]
 pub enum TropicalInt {
     AdditiveIdentity,
@@ -27,12 +35,113 @@ impl TropicalInt {
         D: Into<i64>,
     {
         match self {
-            Self::Integer(int) => Self::new(int.clone() * power.into()),
-            x => x.clone(),
+            Self::Integer(int) => Self::new(*int * power.into()),
+            x => *x,
+        }
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, TropicalError> {
+        match (self, rhs) {
+            (Self::Integer(a), Self::Integer(b)) => a
+                .checked_add(b)
+                .map(Self::Integer)
+                .ok_or(TropicalError::Overflow),
+            _ => Ok(Self::AdditiveIdentity),
+        }
+    }
+
+    pub fn checked_pow(self, power: Degree) -> Result<Self, TropicalError> {
+        match self {
+            Self::Integer(int) => Degree::from(int)
+                .checked_mul(power)
+                .ok_or(TropicalError::Overflow)
+                .and_then(checked_narrow_degree)
+                .map(Self::Integer),
+            x => Ok(x),
+        }
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, TropicalError> {
+        match (self, rhs) {
+            (_, Self::AdditiveIdentity) => Err(TropicalError::DivisionByAdditiveIdentity),
+            (Self::Integer(a), Self::Integer(b)) => a
+                .checked_sub(b)
+                .map(Self::Integer)
+                .ok_or(TropicalError::Overflow),
+            (Self::AdditiveIdentity, _) => Ok(Self::AdditiveIdentity),
+        }
+    }
+}
+
+impl Semiring for TropicalInt {
+    fn additive_identity() -> Self {
+        Self::AdditiveIdentity
+    }
+
+    fn multiplicative_identity() -> Self {
+        Self::zero()
+    }
+
+    fn pow(&self, power: Degree) -> Self {
+        TropicalInt::pow(self, narrow_degree(power))
+    }
+}
+
+impl CheckedSemiring for TropicalInt {
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        TropicalInt::checked_mul(*self, *rhs)
+    }
+
+    fn checked_pow(&self, power: Degree) -> Result<Self, TropicalError> {
+        TropicalInt::checked_pow(*self, power)
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        TropicalInt::checked_div(*self, *rhs)
+    }
+}
+
+impl Semifield for TropicalInt {
+    fn inverse(&self) -> Option<Self> {
+        match self {
+            Self::Integer(int) => Some(Self::Integer(-int)),
+            Self::AdditiveIdentity => None,
+        }
+    }
+
+    fn root(&self, index: Degree) -> Option<Self> {
+        if index == 0 {
+            return None;
+        }
+        match self {
+            Self::Integer(int) => {
+                let index = checked_narrow_degree(index).ok()?;
+                (int % index == 0).then(|| Self::Integer(int / index))
+            }
+            Self::AdditiveIdentity => Some(Self::AdditiveIdentity),
         }
     }
 }
 
+impl TropicalScalar for TropicalInt {
+    fn to_rational(&self) -> Option<BigRational> {
+        match self {
+            Self::Integer(int) => Some(rational(*int)),
+            Self::AdditiveIdentity => None,
+        }
+    }
+
+    fn from_rational_floor(value: &BigRational) -> Self {
+        Self::Integer(
+            value
+                .floor()
+                .to_integer()
+                .to_i64()
+                .expect("value doesn't fit in an i64"),
+        )
+    }
+}
+
 impl PartialEq for TropicalInt {
     fn eq(&self, rhs: &Self) -> bool {
         match (self, rhs) {
@@ -77,7 +186,7 @@ impl Add for TropicalInt {
 impl AddAssign for TropicalInt {
     fn add_assign(&mut self, rhs: Self) {
         // TODO: can we avoid cloning?
-        *self = self.clone() + rhs;
+        *self = *self + rhs;
     }
 }
 
@@ -95,7 +204,7 @@ impl Mul for TropicalInt {
 
 impl MulAssign for TropicalInt {
     fn mul_assign(&mut self, rhs: Self) {
-        *self = self.clone() * rhs;
+        *self = *self * rhs;
     }
 }
 
@@ -105,7 +214,7 @@ impl Div for TropicalInt {
         match (self, rhs) {
             #[allow(clippy::suspicious_arithmetic_impl)]
             (Self::Integer(a), Self::Integer(b)) => Self::Integer(a - b),
-            // FIXME: change Output to Result instead?
+            // README: use `checked_div` to get an error instead
             (_, Self::AdditiveIdentity) => panic!("div by -inf"),
             (Self::AdditiveIdentity, _) => Self::AdditiveIdentity,
         }
@@ -114,7 +223,7 @@ impl Div for TropicalInt {
 
 impl DivAssign for TropicalInt {
     fn div_assign(&mut self, rhs: Self) {
-        *self = self.clone() / rhs;
+        *self = *self / rhs;
     }
 }
 
@@ -142,6 +251,11 @@ impl Sum for TropicalInt {
 #[cfg(test)]
 mod tests {
     use super::TropicalInt;
+    use crate::{
+        error::TropicalError,
+        linear_program::rational,
+        semiring::{Semifield, Semiring, TropicalScalar},
+    };
 
     #[test]
     fn test_tropical_plus() {
@@ -162,4 +276,71 @@ mod tests {
             assert_eq!(a + b, c);
         }
     }
+
+    #[test]
+    fn test_semiring() {
+        let test_table: Vec<(TropicalInt, Option<TropicalInt>, Option<i64>)> = vec![
+            (TropicalInt::from(3), Some(TropicalInt::from(-3)), Some(3)),
+            (TropicalInt::from(0), Some(TropicalInt::from(0)), Some(0)),
+            (TropicalInt::AdditiveIdentity, None, None),
+        ];
+
+        for (a, inverse, value) in test_table {
+            assert_eq!(a.inverse(), inverse);
+            assert_eq!(a.to_rational(), value.map(rational));
+            assert_eq!(a + TropicalInt::additive_identity(), a);
+            assert_eq!(a * TropicalInt::multiplicative_identity(), a);
+        }
+
+        assert_eq!(
+            TropicalInt::from_rational_floor(&(rational(7) / rational(2))),
+            TropicalInt::from(3)
+        );
+        assert_eq!(
+            Semiring::pow(&TropicalInt::from(2), 3),
+            TropicalInt::from(6)
+        );
+        assert_eq!(TropicalInt::from(6).root(3), Some(TropicalInt::from(2)));
+        assert_eq!(TropicalInt::from(6).root(0), None);
+    }
+
+    #[test]
+    fn test_checked() {
+        let max = TropicalInt::from(i64::MAX);
+        let test_table: Vec<(TropicalInt, TropicalInt, Result<TropicalInt, TropicalError>)> = vec![
+            (
+                TropicalInt::from(3),
+                TropicalInt::from(5),
+                Ok(TropicalInt::from(8)),
+            ),
+            (max, TropicalInt::from(1), Err(TropicalError::Overflow)),
+            (
+                max,
+                TropicalInt::AdditiveIdentity,
+                Ok(TropicalInt::AdditiveIdentity),
+            ),
+        ];
+
+        for (a, b, product) in test_table {
+            assert_eq!(a.checked_mul(b), product);
+        }
+
+        assert_eq!(
+            TropicalInt::from(3).checked_pow(4),
+            Ok(TropicalInt::from(12))
+        );
+        assert_eq!(max.checked_pow(2), Err(TropicalError::Overflow));
+        assert_eq!(
+            TropicalInt::from(3).checked_div(TropicalInt::from(5)),
+            Ok(TropicalInt::from(-2))
+        );
+        assert_eq!(
+            TropicalInt::from(i64::MIN).checked_div(TropicalInt::from(1)),
+            Err(TropicalError::Overflow)
+        );
+        assert_eq!(
+            max.checked_div(TropicalInt::AdditiveIdentity),
+            Err(TropicalError::DivisionByAdditiveIdentity)
+        );
+    }
 }
@@ -0,0 +1,481 @@
+/*
+    README: max-plus matrices, (AB)_ij = max_k (A_ik + B_kj). the identity has 0 on the
+    diagonal and -∞ everywhere else. matrix based protocols like the Grigoriev–Shpilrain key
+    exchange only need products and powers, and powers of the same matrix commute.
+*/
+use core::fmt;
+use core::ops::{Add, Index, IndexMut, Mul};
+
+use crate::semiring::Semiring;
+use crate::tropical_int::TropicalInt;
+use crate::tropical_polynomial::Degree;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TropicalMatrix<const R: usize, const C: usize, T = TropicalInt> {
+    pub(crate) entries: [[T; C]; R],
+}
+
+impl<const R: usize, const C: usize, T: Semiring> TropicalMatrix<R, C, T> {
+    pub fn new(entries: [[T; C]; R]) -> Self {
+        Self { entries }
+    }
+
+    /// Every entry is -∞, the additive identity.
+    pub fn additive_identity() -> Self {
+        Self::new(core::array::from_fn(|_| {
+            core::array::from_fn(|_| T::additive_identity())
+        }))
+    }
+
+    pub fn transpose(&self) -> TropicalMatrix<C, R, T> {
+        TropicalMatrix::new(core::array::from_fn(|i| {
+            core::array::from_fn(|j| self.entries[j][i].clone())
+        }))
+    }
+
+    pub fn rows(&self) -> &[[T; C]; R] {
+        &self.entries
+    }
+}
+
+impl<const N: usize, T: Semiring> TropicalMatrix<N, N, T> {
+    pub fn identity() -> Self {
+        Self::new(core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                if i == j {
+                    T::multiplicative_identity()
+                } else {
+                    T::additive_identity()
+                }
+            })
+        }))
+    }
+
+    // README: square and multiply, the product is associative so the order doesn't matter
+    pub fn pow(&self, power: Degree) -> Self {
+        debug_assert!(power >= 0, "negative powers need an inverse matrix");
+        let mut result = Self::identity();
+        let mut square = self.clone();
+        let mut power = power;
+
+        while power > 0 {
+            if power & 1 == 1 {
+                result = &result * &square;
+            }
+            square = &square * &square;
+            power >>= 1;
+        }
+
+        result
+    }
+}
+
+/// `max_σ sum_i a_iσ(i)`, which is also the tropical permanent. The matrix is singular when
+/// the maximum is attained by more than one permutation, or when it's -∞.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TropicalDeterminant<const N: usize> {
+    pub value: TropicalInt,
+    /// `permutation[i]` is the column of row `i` in an optimal permutation.
+    pub permutation: [usize; N],
+    pub singular: bool,
+}
+
+impl<const N: usize> TropicalMatrix<N, N> {
+    /*
+        README: the determinant is an assignment problem, we solve it with the hungarian
+        algorithm on the costs -a_ij (-∞ entries get a cost larger than any assignment
+        without them). the dual potentials u_i + v_j <= cost_ij are tight on every optimal
+        permutation, so the optimum is attained twice iff the tight edges have an alternating
+        cycle: row i -> row p(j) for every tight (i, j) that isn't in the permutation.
+    */
+    pub fn tropical_det(&self) -> TropicalDeterminant<N> {
+        let finite = |i: usize, j: usize| match self.entries[i][j] {
+            TropicalInt::Integer(a) => Some(i128::from(a)),
+            TropicalInt::AdditiveIdentity => None,
+        };
+        let largest = (0..N)
+            .flat_map(|i| (0..N).filter_map(move |j| finite(i, j)))
+            .map(i128::abs)
+            .max()
+            .unwrap_or(0);
+        let forbidden = 2 * (N as i128 + 1) * (largest + 1);
+        let cost = |i: usize, j: usize| finite(i, j).map_or(forbidden, |a| -a);
+
+        let (row_of_column, row_potentials, column_potentials) = hungarian::<N>(cost);
+
+        let mut permutation = [0; N];
+        for (column, row) in row_of_column.iter().enumerate() {
+            permutation[*row] = column;
+        }
+        let value = permutation
+            .iter()
+            .enumerate()
+            .map(|(row, column)| self.entries[row][*column])
+            .fold(TropicalInt::zero(), |acc, entry| acc * entry);
+
+        let singular = value == TropicalInt::AdditiveIdentity || {
+            let tight = |i: usize, j: usize| {
+                finite(i, j).is_some() && cost(i, j) == row_potentials[i] + column_potentials[j]
+            };
+            has_cycle::<N>(|i| {
+                (0..N)
+                    .filter(|&j| j != permutation[i] && tight(i, j))
+                    .map(|j| row_of_column[j])
+                    .collect()
+            })
+        };
+
+        TropicalDeterminant {
+            value,
+            permutation,
+            singular,
+        }
+    }
+}
+
+// README: the O(N³) version with potentials, returns the row of every column and the
+// potentials of the rows and columns
+fn hungarian<const N: usize>(
+    cost: impl Fn(usize, usize) -> i128,
+) -> ([usize; N], Vec<i128>, Vec<i128>) {
+    // index 0 is a dummy column to start the augmenting paths from
+    let mut u = vec![0; N + 1];
+    let mut v = vec![0; N + 1];
+    let mut row_of = vec![0; N + 1];
+    let mut way = vec![0; N + 1];
+
+    for row in 1..=N {
+        row_of[0] = row;
+        let mut column = 0;
+        let mut min_slack = vec![i128::MAX; N + 1];
+        let mut used = vec![false; N + 1];
+
+        loop {
+            used[column] = true;
+            let current_row = row_of[column];
+            let mut delta = i128::MAX;
+            let mut next_column = 0;
+
+            for j in 1..=N {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost(current_row - 1, j - 1) - u[current_row] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = column;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    next_column = j;
+                }
+            }
+
+            for j in 0..=N {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+
+            column = next_column;
+            if row_of[column] == 0 {
+                break;
+            }
+        }
+
+        while column != 0 {
+            let previous = way[column];
+            row_of[column] = row_of[previous];
+            column = previous;
+        }
+    }
+
+    (
+        core::array::from_fn(|j| row_of[j + 1] - 1),
+        u[1..].to_vec(),
+        v[1..].to_vec(),
+    )
+}
+
+fn has_cycle<const N: usize>(successors: impl Fn(usize) -> Vec<usize>) -> bool {
+    // 0 unvisited, 1 on the stack, 2 done
+    let mut state = [0u8; N];
+
+    fn visit(node: usize, state: &mut [u8], successors: &dyn Fn(usize) -> Vec<usize>) -> bool {
+        state[node] = 1;
+        for next in successors(node) {
+            if state[next] == 1 || (state[next] == 0 && visit(next, state, successors)) {
+                return true;
+            }
+        }
+        state[node] = 2;
+        false
+    }
+
+    (0..N).any(|node| state[node] == 0 && visit(node, &mut state, &successors))
+}
+
+impl<const R: usize, const C: usize, T> Index<(usize, usize)> for TropicalMatrix<R, C, T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self.entries[row][column]
+    }
+}
+
+impl<const R: usize, const C: usize, T> IndexMut<(usize, usize)> for TropicalMatrix<R, C, T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self.entries[row][column]
+    }
+}
+
+impl<const R: usize, const C: usize, T: Semiring> Add for TropicalMatrix<R, C, T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(core::array::from_fn(|i| {
+            core::array::from_fn(|j| self.entries[i][j].clone() + rhs.entries[i][j].clone())
+        }))
+    }
+}
+
+impl<const R: usize, const K: usize, const C: usize, T: Semiring> Mul<&TropicalMatrix<K, C, T>>
+    for &TropicalMatrix<R, K, T>
+{
+    type Output = TropicalMatrix<R, C, T>;
+
+    fn mul(self, rhs: &TropicalMatrix<K, C, T>) -> Self::Output {
+        TropicalMatrix::new(core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                (0..K).fold(T::additive_identity(), |acc, k| {
+                    acc + self.entries[i][k].clone() * rhs.entries[k][j].clone()
+                })
+            })
+        }))
+    }
+}
+
+impl<const R: usize, const K: usize, const C: usize, T: Semiring> Mul<TropicalMatrix<K, C, T>>
+    for TropicalMatrix<R, K, T>
+{
+    type Output = TropicalMatrix<R, C, T>;
+
+    fn mul(self, rhs: TropicalMatrix<K, C, T>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<const R: usize, const C: usize, T: fmt::Display> fmt::Display for TropicalMatrix<R, C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.entries.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, entry) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{entry}")?;
+            }
+            write!(f, "]")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const R: usize, const C: usize> From<[[i64; C]; R]> for TropicalMatrix<R, C> {
+    fn from(entries: [[i64; C]; R]) -> Self {
+        Self::new(entries.map(|row| row.map(TropicalInt::from)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng as _;
+
+    use super::TropicalMatrix;
+    use crate::tropical_int::TropicalInt;
+
+    #[test]
+    fn test_product() {
+        let test_table: Vec<[TropicalMatrix<2, 2>; 3]> = vec![
+            [
+                TropicalMatrix::from([[1, 2], [3, 4]]),
+                TropicalMatrix::from([[0, -1], [5, 2]]),
+                TropicalMatrix::from([[7, 4], [9, 6]]),
+            ],
+            [
+                TropicalMatrix::from([[1, 2], [3, 4]]),
+                TropicalMatrix::identity(),
+                TropicalMatrix::from([[1, 2], [3, 4]]),
+            ],
+            [
+                TropicalMatrix::from([[1, 2], [3, 4]]),
+                TropicalMatrix::additive_identity(),
+                TropicalMatrix::additive_identity(),
+            ],
+        ];
+
+        for [a, b, product] in test_table {
+            assert_eq!(&a * &b, product);
+        }
+
+        // 2x3 times 3x1
+        let a = TropicalMatrix::from([[0, 1, 2], [-1, 0, 3]]);
+        let b = TropicalMatrix::from([[4], [2], [0]]);
+        assert_eq!(a * b, TropicalMatrix::from([[4], [3]]));
+    }
+
+    #[test]
+    fn test_pow_and_transpose() {
+        let a = TropicalMatrix::from([[0, 3, -2], [1, -1, 0], [2, 4, 1]]);
+
+        assert_eq!(a.pow(0), TropicalMatrix::identity());
+        assert_eq!(a.pow(1), a);
+        assert_eq!(
+            a.pow(5),
+            (0..5).fold(TropicalMatrix::identity(), |acc, _| &acc * &a)
+        );
+        assert_eq!(&a.pow(3) * &a.pow(4), a.pow(7));
+
+        let b = TropicalMatrix::from([[0, 1, 2], [-1, 0, 3]]);
+        assert_eq!(
+            b.transpose(),
+            TropicalMatrix::from([[0, -1], [1, 0], [2, 3]])
+        );
+        // (AB)^T = B^T A^T since ⊙ commutes
+        assert_eq!((&b * &a).transpose(), &a.transpose() * &b.transpose());
+    }
+
+    #[test]
+    fn test_key_exchange() {
+        // Grigoriev–Shpilrain with monomials instead of polynomials in A and B
+        let a = TropicalMatrix::from([[2, -3, 0], [5, 1, -1], [0, 4, 3]]);
+        let b = TropicalMatrix::from([[-1, 0, 7], [2, 2, -5], [3, -2, 0]]);
+        let (alice_a, alice_b) = (5, 3);
+        let (bob_a, bob_b) = (2, 6);
+
+        let alice_public = &a.pow(alice_a) * &b.pow(alice_b);
+        let bob_public = &a.pow(bob_a) * &b.pow(bob_b);
+
+        let alice_key = &(&a.pow(alice_a) * &bob_public) * &b.pow(alice_b);
+        let bob_key = &(&a.pow(bob_a) * &alice_public) * &b.pow(bob_b);
+        assert_eq!(alice_key, bob_key);
+    }
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        permutations(n - 1)
+            .into_iter()
+            .flat_map(|permutation| {
+                (0..n).map(move |position| {
+                    let mut permutation = permutation.clone();
+                    permutation.insert(position, n - 1);
+                    permutation
+                })
+            })
+            .collect()
+    }
+
+    fn check_tropical_det<const N: usize>(matrix: &TropicalMatrix<N, N>) {
+        let values: Vec<TropicalInt> = permutations(N)
+            .iter()
+            .map(|permutation| {
+                (0..N).fold(TropicalInt::zero(), |acc, i| {
+                    acc * matrix[(i, permutation[i])]
+                })
+            })
+            .collect();
+        let best = values.iter().copied().sum::<TropicalInt>();
+        let attained = values.iter().filter(|value| **value == best).count();
+
+        let det = matrix.tropical_det();
+        assert_eq!(det.value, best);
+        assert_eq!(
+            det.singular,
+            attained > 1 || best == TropicalInt::AdditiveIdentity
+        );
+        assert_eq!(
+            (0..N).fold(TropicalInt::zero(), |acc, i| acc
+                * matrix[(i, det.permutation[i])]),
+            best
+        );
+    }
+
+    #[test]
+    fn test_tropical_det() {
+        let bottom = TropicalInt::AdditiveIdentity;
+        let test_table: Vec<(TropicalMatrix<3, 3>, TropicalInt, [usize; 3], bool)> = vec![
+            (
+                TropicalMatrix::identity(),
+                TropicalInt::zero(),
+                [0, 1, 2],
+                false,
+            ),
+            (
+                TropicalMatrix::from([[1, 5, 0], [4, 2, 0], [0, 0, 3]]),
+                TropicalInt::from(12),
+                [1, 0, 2],
+                false,
+            ),
+            // 0 + 0 + 0 both ways
+            (
+                TropicalMatrix::from([[0, 0, -5], [0, 0, -5], [-5, -5, 0]]),
+                TropicalInt::zero(),
+                [0, 1, 2],
+                true,
+            ),
+            (
+                TropicalMatrix::new([
+                    [bottom, TropicalInt::from(2), bottom],
+                    [bottom, TropicalInt::from(7), bottom],
+                    [TropicalInt::from(1), bottom, TropicalInt::from(4)],
+                ]),
+                bottom,
+                [1, 0, 2],
+                true,
+            ),
+        ];
+
+        for (matrix, value, permutation, singular) in test_table {
+            let det = matrix.tropical_det();
+            assert_eq!(det.value, value);
+            assert_eq!(det.singular, singular);
+            if value != bottom {
+                assert_eq!(det.permutation, permutation);
+            }
+            check_tropical_det(&matrix);
+        }
+    }
+
+    #[test]
+    fn test_tropical_det_against_permutations() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            // small entries so ties and singular matrices show up
+            let matrix: TropicalMatrix<5, 5> = TropicalMatrix::new(core::array::from_fn(|_| {
+                core::array::from_fn(|_| match rng.gen_range(-3..=4) {
+                    4 => TropicalInt::AdditiveIdentity,
+                    entry => TropicalInt::from(entry),
+                })
+            }));
+            check_tropical_det(&matrix);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let mut a = TropicalMatrix::from([[1, 2], [3, 4]]);
+        a[(0, 1)] = TropicalInt::AdditiveIdentity;
+
+        assert_eq!(a.to_string(), "[1, -∞]\n[3, 4]");
+        assert_eq!(a[(1, 0)], TropicalInt::from(3));
+    }
+}
@@ -1,97 +1,190 @@
+use core::cmp::Reverse;
 use core::fmt;
 use core::ops::{Add, AddAssign, Mul, MulAssign};
-use std::collections::HashMap;
 use std::iter::Sum;
 
+use num_rational::BigRational;
+use rayon::prelude::*;
+
+use crate::error::TropicalError;
+use crate::linear_program::{maximize, rational, LinearProgram};
+use crate::monomial_order::MonomialOrder;
+use crate::semiring::{CheckedSemiring, Semiring, TropicalScalar};
+use crate::terms::{TermStore, Terms};
 use crate::tropical_int::TropicalInt;
 
 // README: not ideal but I need to have inverse for automorphisms so...
+// the multidegrees are hashmap keys and get copied around everywhere, so instead of a big
+// int the `wide-degrees` feature gives 128 bits for exponents that don't fit in 64.
+// that's still a bound, there are no arbitrary precision degrees: past i128 the plain
+// operations overflow and the `try_` ones return `TropicalError::Overflow`
+#[cfg(not(feature = "wide-degrees"))]
 pub type Degree = i64;
+#[cfg(feature = "wide-degrees")]
+pub type Degree = i128;
+
+// README: for the places where a degree ends up in an i64 coefficient
+pub(crate) fn narrow_degree(degree: Degree) -> i64 {
+    checked_narrow_degree(degree).expect("degree doesn't fit in an i64")
+}
+
+pub(crate) fn gcd(a: Degree, b: Degree) -> Degree {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[allow(clippy::useless_conversion)]
+pub(crate) fn checked_narrow_degree(degree: Degree) -> Result<i64, TropicalError> {
+    i64::try_from(i128::from(degree)).map_err(|_| TropicalError::Overflow)
+}
 
 #[derive(Clone, Debug)]
-pub struct TropicalPolynomial<const N: usize> {
-    pub(crate) terms: HashMap<[Degree; N], TropicalInt>,
+pub struct TropicalPolynomial<const N: usize, T = TropicalInt> {
+    pub(crate) terms: Terms<N, T>,
 }
 
-impl<const N: usize> TropicalPolynomial<N> {
-    pub fn new() -> Self {
+impl<const N: usize, T> Default for TropicalPolynomial<N, T> {
+    fn default() -> Self {
         TropicalPolynomial {
-            terms: HashMap::new(),
+            terms: Terms::default(),
         }
     }
+}
+
+impl<const N: usize, T> TropicalPolynomial<N, T> {
+    /// The terms from the largest to the smallest degree under `order`.
+    pub fn terms_sorted(&self, order: MonomialOrder) -> Vec<(&[Degree; N], &T)> {
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_by(|a, b| order.compare(b.0, a.0));
+        terms
+    }
+
+    pub fn leading_term(&self, order: MonomialOrder) -> Option<(&[Degree; N], &T)> {
+        self.terms.iter().max_by(|a, b| order.compare(a.0, b.0))
+    }
+
+    /// The degree of the leading term.
+    pub fn leading_monomial(&self, order: MonomialOrder) -> Option<[Degree; N]> {
+        self.leading_term(order)
+            .map(|(multi_degree, _)| *multi_degree)
+    }
+
+    pub fn trailing_term(&self, order: MonomialOrder) -> Option<(&[Degree; N], &T)> {
+        self.terms.iter().min_by(|a, b| order.compare(a.0, b.0))
+    }
+}
+
+impl<const N: usize, T: Semiring> TropicalPolynomial<N, T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    pub fn add_term(&mut self, multi_degree: [Degree; N], coefficient: TropicalInt) {
+    pub fn add_term(&mut self, multi_degree: [Degree; N], coefficient: T) {
         let current_coefficient = self
             .terms
             .get(&multi_degree)
-            .unwrap_or(&TropicalInt::AdditiveIdentity);
+            .cloned()
+            .unwrap_or_else(T::additive_identity);
 
-        if coefficient + *current_coefficient == coefficient {
+        if coefficient.clone() + current_coefficient == coefficient {
             self.terms.insert(multi_degree, coefficient);
         }
     }
 
-    pub fn get_term(&self, multi_degree: &[Degree; N]) -> Option<&TropicalInt> {
+    pub fn get_term(&self, multi_degree: &[Degree; N]) -> Option<&T> {
         self.terms.get(multi_degree)
     }
 
-    pub fn monomial(multi_degree: [Degree; N], coefficent: TropicalInt) -> Self {
+    pub fn monomial(multi_degree: [Degree; N], coefficent: T) -> Self {
         Self::from(vec![(multi_degree, coefficent)])
     }
 
     pub fn variable(index: usize) -> Self {
         TropicalPolynomial::from(vec![(
             core::array::from_fn(|inner| if inner == index { 1 } else { 0 }),
-            TropicalInt::from(0),
+            T::multiplicative_identity(),
         )])
     }
 
-    pub fn constant(constant: TropicalInt) -> Self {
+    pub fn constant(constant: T) -> Self {
         Self::monomial(core::array::from_fn(|_| 0), constant)
     }
 
     pub fn additive_identity() -> Self {
-        Self::constant(TropicalInt::AdditiveIdentity)
+        Self::constant(T::additive_identity())
     }
 
     pub fn multiplicative_identity() -> Self {
-        Self::constant(TropicalInt::from(0))
+        Self::constant(T::multiplicative_identity())
     }
 
     pub fn pow(&self, power: Degree) -> Self {
+        self.square_and_multiply(power, |product| product)
+    }
+
+    // README: square and multiply, `prune` runs on every product so we can keep them small
+    fn square_and_multiply(&self, power: Degree, prune: impl Fn(Self) -> Self) -> Self {
+        let mut result = Self::multiplicative_identity();
+        let mut square = self.clone();
+        let mut power = power;
+
+        while power > 0 {
+            if power & 1 == 1 {
+                result = prune(&result * &square);
+            }
+            power >>= 1;
+            if power > 0 {
+                square = prune(&square * &square);
+            }
+        }
+
+        result
+    }
+
+    /// `self^power` as a function. by the frobenius identity `(a + b)^n = a^n + b^n` every
+    /// term is raised on its own, so the result can miss terms of `pow` that are dominated.
+    pub fn pow_functional(&self, power: Degree) -> Self {
+        debug_assert!(power >= 0);
         if power == 0 {
             return Self::multiplicative_identity();
         }
 
-        // TODO: parallelize
-        (1..=power).fold(Self::multiplicative_identity(), |acc, _| {
-            acc.mul(self.clone())
-        })
+        Self::from(
+            self.terms
+                .iter()
+                .map(|(multi_degree, coefficient)| {
+                    (
+                        core::array::from_fn(|index| multi_degree[index] * power),
+                        coefficient.pow(power),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
 
-        // README: this is more efficient for evaluation
-        // but not for formally computing the polynomial terms
-        // Self::from(
-        //     self.terms
-        //         .iter()
-        //         .map(|(multi_degree, coefficient)| {
-        //             (
-        //                 core::array::from_fn(|index| multi_degree[index] * power),
-        //                 coefficient.pow(power),
-        //             )
-        //         })
-        //         .collect::<Vec<([Degree; N], TropicalInt)>>(),
-        // )
-    }
-
-    pub fn evaluate(&self, variables: [TropicalInt; N]) -> TropicalInt {
+    /// Maps every coefficient, dropping the terms that become the additive identity.
+    pub fn map_coefficients<U: Semiring>(&self, f: impl Fn(&T) -> U) -> TropicalPolynomial<N, U> {
+        TropicalPolynomial::from(
+            self.terms
+                .iter()
+                .map(|(multi_degree, coefficient)| (*multi_degree, f(coefficient)))
+                .filter(|(_, coefficient)| !coefficient.is_additive_identity())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn evaluate(&self, variables: [T; N]) -> T {
         self.terms.iter().fold(
-            TropicalInt::AdditiveIdentity,
+            T::additive_identity(),
             |acc, (multi_degree, coefficient)| {
                 acc + coefficient.clone()
                     * variables
                         .iter()
                         .zip(multi_degree.iter())
-                        .fold(TropicalInt::zero(), |term, (variable, &degree)| {
+                        .fold(T::multiplicative_identity(), |term, (variable, &degree)| {
                             term * variable.pow(degree)
                         })
             },
@@ -99,92 +192,418 @@ impl<const N: usize> TropicalPolynomial<N> {
     }
 }
 
-// TODO: implement for array and slices
-impl<const N: usize> From<Vec<([Degree; N], TropicalInt)>> for TropicalPolynomial<N> {
-    fn from(terms: Vec<([Degree; N], TropicalInt)>) -> Self {
-        let mut result = HashMap::new();
-        for term in terms {
-            let current_coefficient = result
-                .get(&term.0)
-                .unwrap_or(&TropicalInt::AdditiveIdentity);
-
-            if term.1 + *current_coefficient == term.1 {
-                result.insert(term.0, term.1);
+impl<const N: usize, T: CheckedSemiring> TropicalPolynomial<N, T> {
+    /// `self * rhs`, with an error instead of overflowing the coefficients or the degrees.
+    pub fn try_mul(&self, rhs: &Self) -> Result<Self, TropicalError> {
+        let mut result = Self::new();
+
+        for (exponents1, coefficient1) in self.terms.iter() {
+            for (exponents2, coefficient2) in rhs.terms.iter() {
+                let mut new_exponents = [0; N];
+                for i in 0..N {
+                    new_exponents[i] = exponents1[i]
+                        .checked_add(exponents2[i])
+                        .ok_or(TropicalError::Overflow)?;
+                }
+
+                let coefficient_increment = coefficient1.checked_mul(coefficient2)?;
+                if coefficient_increment.is_additive_identity() {
+                    continue;
+                }
+
+                result.add_term(new_exponents, coefficient_increment);
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn try_pow(&self, power: Degree) -> Result<Self, TropicalError> {
+        let mut result = Self::multiplicative_identity();
+        let mut square = self.clone();
+        let mut power = power;
+
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.try_mul(&square)?;
+            }
+            power >>= 1;
+            if power > 0 {
+                square = square.try_mul(&square)?;
             }
         }
 
-        Self { terms: result }
+        Ok(result)
+    }
+}
+
+impl<const N: usize, T: TropicalScalar> TropicalPolynomial<N, T> {
+    /// Keeps only the terms that are vertices of the upper convex hull of the lifted support
+    /// `{(degree, coefficient)}`. Every other term is never strictly the maximum at any point,
+    /// so polynomials which are equal as functions get identical normal forms.
+    pub fn normal_form(&self) -> Self {
+        Self::from(
+            upper_hull_vertices(&self.lifted_terms())
+                .into_iter()
+                .map(|multi_degree| (multi_degree, self.terms[&multi_degree].clone()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// `self^power` with the dominated terms dropped after every product, which is
+    /// `pow(power).normal_form()` without ever holding all the terms of `pow`.
+    pub fn pow_pruned(&self, power: Degree) -> Self {
+        self.normal_form()
+            .square_and_multiply(power, |product| product.normal_form())
+    }
+
+    /// Compares both polynomials as functions, which is what `PartialEq` can't do since it
+    /// compares the terms syntactically.
+    pub fn functionally_eq(&self, other: &Self) -> bool {
+        self == other || self.normal_form() == other.normal_form()
+    }
+
+    /*
+        README: tropical division following "Revisiting Tropical Polynomial Division".
+        the quotient is the largest polynomial with quotient * divisor <= self (see
+        `residual`), and the remainder keeps the terms of self's normal form that the
+        product doesn't reach, so self = quotient * divisor + remainder as functions.
+    */
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), TropicalError> {
+        let dividend = self.normal_form();
+        let divisor = divisor.normal_form();
+        if divisor.terms.is_empty() {
+            return Err(TropicalError::DivisionByAdditiveIdentity);
+        }
+
+        let quotient = dividend.residual(&divisor).normal_form();
+        let product = (quotient.clone() * divisor).lifted_terms();
+
+        let remainder = Self::from(
+            dividend
+                .lifted_terms()
+                .into_par_iter()
+                .filter(|(multi_degree, coefficient)| {
+                    upper_hull_height(&product, *multi_degree)
+                        .is_none_or(|height| height < *coefficient)
+                })
+                .map(|(multi_degree, _)| (multi_degree, dividend.terms[&multi_degree].clone()))
+                .collect::<Vec<_>>(),
+        );
+
+        Ok((quotient, remainder))
+    }
+
+    /// Returns `r` such that `self = divisor * r` as functions, if there is one.
+    pub fn exact_quotient(&self, divisor: &Self) -> Option<Self> {
+        let (quotient, remainder) = self.div_rem(divisor).ok()?;
+        remainder.terms.is_empty().then_some(quotient)
+    }
+
+    pub fn is_divisible_by(&self, divisor: &Self) -> bool {
+        self.exact_quotient(divisor).is_some()
+    }
+
+    /*
+        README: this is the largest polynomial r supported on {d_p - d_q} (d >= 0) such that
+        r * divisor <= self pointwise. every term of r * divisor has to lie below the upper
+        hull of self, so the coefficient of r at e is min_q(h(e + d_q) - c_q) where h is the
+        height of the upper hull of self (and the term is dropped if e + d_q falls outside the
+        newton polytope)
+    */
+    pub(crate) fn residual(&self, divisor: &Self) -> Self {
+        let terms = self.lifted_terms();
+        let divisor_terms = divisor.lifted_terms();
+
+        let mut candidates: Vec<[Degree; N]> = terms
+            .iter()
+            .flat_map(|(multi_degree, _)| {
+                divisor_terms.iter().map(|(divisor_degree, _)| {
+                    core::array::from_fn(|i| multi_degree[i] - divisor_degree[i])
+                })
+            })
+            .collect();
+        // README: we divide in the polynomial semiring, not over laurent polynomials,
+        // otherwise any monomial would divide anything
+        candidates.retain(|candidate| candidate.iter().all(|d| *d >= 0));
+        candidates.sort();
+        candidates.dedup();
+
+        Self::from(
+            candidates
+                .into_par_iter()
+                .filter_map(|candidate| {
+                    divisor_terms
+                        .iter()
+                        .map(|(divisor_degree, divisor_coefficient)| {
+                            let height = upper_hull_height(
+                                &terms,
+                                core::array::from_fn(|i| candidate[i] + divisor_degree[i]),
+                            )?;
+                            Some(height - divisor_coefficient)
+                        })
+                        .collect::<Option<Vec<BigRational>>>()?
+                        .into_iter()
+                        .min()
+                        .map(|coefficient| (candidate, T::from_rational_floor(&coefficient)))
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    // README: the terms as points (degree, max-plus value) of the lifted support
+    pub(crate) fn lifted_terms(&self) -> Vec<([Degree; N], BigRational)> {
+        self.terms
+            .iter()
+            .filter_map(|(multi_degree, coefficient)| {
+                Some((*multi_degree, coefficient.to_rational()?))
+            })
+            .collect()
+    }
+}
+
+/*
+    README: the height of the upper hull of the lifted terms at a given degree is
+    max sum(l_j c_j) st. sum(l_j d_j) = d, sum(l_j) = 1, l_j >= 0
+    and it's undefined outside of the newton polytope
+*/
+fn upper_hull_height<'a, const N: usize>(
+    terms: impl IntoIterator<Item = &'a ([Degree; N], BigRational)>,
+    multi_degree: [Degree; N],
+) -> Option<BigRational> {
+    let terms: Vec<_> = terms.into_iter().collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    let constraints: Vec<_> = (0..=N)
+        .map(|row| {
+            terms
+                .iter()
+                .map(|(term_degree, _)| rational(if row < N { term_degree[row] } else { 1 }))
+                .collect()
+        })
+        .collect();
+    let bounds: Vec<_> = (0..=N)
+        .map(|row| rational(if row < N { multi_degree[row] } else { 1 }))
+        .collect();
+    let costs: Vec<_> = terms
+        .iter()
+        .map(|(_, coefficient)| coefficient.clone())
+        .collect();
+
+    match maximize(&constraints, &bounds, &costs) {
+        LinearProgram::Optimal { value, .. } => Some(value),
+        LinearProgram::Infeasible => None,
+        LinearProgram::Unbounded => unreachable!("the convex hull of the support is bounded"),
+    }
+}
+
+/*
+    README: checking every term against all the others is one lp over the whole support per
+    term, so first we take a few terms that are vertices for sure and drop everything below
+    the hull of those alone, which is a tiny lp. the sure ones are the first and last in lex
+    order among the maxima of c + w.d, and of w.d then c, for w = 0, ±e_i ± e_j. dropping
+    terms below the hull doesn't change it, so the survivors are only checked against each other
+*/
+fn upper_hull_vertices<const N: usize>(terms: &[([Degree; N], BigRational)]) -> Vec<[Degree; N]> {
+    let mut weights: Vec<[Degree; N]> = (0..N)
+        .flat_map(|i| (i..N).map(move |j| (i, j)))
+        .flat_map(|(i, j)| {
+            [(1, 1), (1, -1), (-1, 1), (-1, -1)].map(|(a, b)| {
+                let mut weight = [0; N];
+                weight[i] += a;
+                weight[j] += b;
+                weight
+            })
+        })
+        .collect();
+    weights.sort();
+    weights.dedup();
+
+    let mut vertices: Vec<usize> = weights
+        .iter()
+        .flat_map(|weight| {
+            let slope = |index: &usize| -> Degree {
+                let multi_degree = &terms[*index].0;
+                weight.iter().zip(multi_degree).map(|(w, d)| w * d).sum()
+            };
+            let height = |index: &usize| &terms[*index].1 + rational(slope(index));
+            let degree = |index: &usize| terms[*index].0;
+
+            [
+                (0..terms.len()).max_by_key(|index| (height(index), degree(index))),
+                (0..terms.len()).max_by_key(|index| (height(index), Reverse(degree(index)))),
+                (0..terms.len())
+                    .max_by_key(|index| (slope(index), &terms[*index].1, degree(index))),
+                (0..terms.len())
+                    .max_by_key(|index| (slope(index), &terms[*index].1, Reverse(degree(index)))),
+            ]
+        })
+        .flatten()
+        .collect();
+    vertices.sort();
+    vertices.dedup();
+
+    let mut known: Vec<_> = vertices.iter().map(|&index| terms[index].clone()).collect();
+    let mut undecided: Vec<_> = (0..terms.len())
+        .into_par_iter()
+        .filter(|index| vertices.binary_search(index).is_err())
+        .map(|index| terms[index].clone())
+        .filter(|term| !is_below_upper_hull(&known, term))
+        .collect();
+
+    // README: a new vertex makes the small hull bigger, so it drops more of the others
+    while let Some(term) = undecided.pop() {
+        if !is_below_upper_hull(known.iter().chain(&undecided), &term) {
+            known.push(term);
+            undecided = undecided
+                .into_par_iter()
+                .filter(|term| !is_below_upper_hull(&known, term))
+                .collect();
+        }
+    }
+
+    known
+        .into_iter()
+        .map(|(multi_degree, _)| multi_degree)
+        .collect()
+}
+
+// README: a lifted term is not a vertex of the upper hull iff the other terms reach it
+fn is_below_upper_hull<'a, const N: usize>(
+    others: impl IntoIterator<Item = &'a ([Degree; N], BigRational)>,
+    (multi_degree, coefficient): &([Degree; N], BigRational),
+) -> bool {
+    upper_hull_height(others, *multi_degree).is_some_and(|height| height >= *coefficient)
+}
+
+/// `leading_coefficient ⊙ x^lowest_degree ⊙ (x ⊕ r_1)^m_1 ⊙ ... ⊙ (x ⊕ r_k)^m_k`, with the roots
+/// in increasing order as max-plus values. Roots can be fractional, but `m_i r_i` never is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearFactorization<T = TropicalInt> {
+    pub leading_coefficient: T,
+    pub lowest_degree: Degree,
+    pub roots: Vec<(BigRational, Degree)>,
+}
+
+impl<T: TropicalScalar> LinearFactorization<T> {
+    /// The factors as polynomials, using `(x ⊕ r)^m = x^m ⊕ mr` so they have integer
+    /// coefficients: the leading monomial first and then one factor per root.
+    pub fn factors(&self) -> Vec<TropicalPolynomial<1, T>> {
+        let mut factors = vec![TropicalPolynomial::monomial(
+            [self.lowest_degree],
+            self.leading_coefficient.clone(),
+        )];
+
+        factors.extend(self.roots.iter().map(|(root, multiplicity)| {
+            TropicalPolynomial::from(vec![
+                ([*multiplicity], T::multiplicative_identity()),
+                (
+                    [0],
+                    T::from_rational_floor(&(root * rational(*multiplicity))),
+                ),
+            ])
+        }));
+
+        factors
+    }
+
+    pub fn expand(&self) -> TropicalPolynomial<1, T> {
+        self.factors().into_iter().fold(
+            TropicalPolynomial::multiplicative_identity(),
+            |acc, factor| acc * factor,
+        )
+    }
+}
+
+impl<T: TropicalScalar> TropicalPolynomial<1, T> {
+    /*
+        README: tropical fundamental theorem of algebra. the vertices of the upper hull sorted
+        by degree give the corners of the function, two consecutive vertices (d, c) and (d', c')
+        meet at the root (c - c') / (d' - d) with multiplicity d' - d
+    */
+    /// The complete factorization into linear factors, `factor` is the heuristic one for any
+    /// number of variables.
+    pub fn factor_linear(&self) -> LinearFactorization<T> {
+        let normal_form = self.normal_form();
+        let mut vertices = normal_form.lifted_terms();
+        vertices.sort_by_key(|([degree], _)| *degree);
+
+        let Some(([highest_degree], _)) = vertices.last() else {
+            return LinearFactorization {
+                leading_coefficient: T::additive_identity(),
+                lowest_degree: 0,
+                roots: vec![],
+            };
+        };
+        debug_assert!(*highest_degree >= vertices[0].0[0]);
+
+        LinearFactorization {
+            leading_coefficient: normal_form.terms[&[*highest_degree]].clone(),
+            lowest_degree: vertices[0].0[0],
+            roots: vertices
+                .windows(2)
+                .map(|pair| {
+                    let [([low_degree], low_coefficient), ([high_degree], high_coefficient)] =
+                        [&pair[0], &pair[1]];
+                    let multiplicity = high_degree - low_degree;
+                    (
+                        (low_coefficient - high_coefficient) / rational(multiplicity),
+                        multiplicity,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+// TODO: implement for array and slices
+impl<const N: usize, T: Semiring> From<Vec<([Degree; N], T)>> for TropicalPolynomial<N, T> {
+    fn from(terms: Vec<([Degree; N], T)>) -> Self {
+        TropicalPolynomial {
+            terms: Terms::from_terms(terms),
+        }
     }
 }
 
-impl<const N: usize> Add for TropicalPolynomial<N> {
+impl<const N: usize, T: Semiring> Add for TropicalPolynomial<N, T> {
     type Output = Self;
 
     // TODO: parallelize?
     fn add(self, rhs: Self) -> Self {
-        let mut result = TropicalPolynomial::new();
-
-        for (exponents, coefficient) in self.terms.iter().chain(rhs.terms.iter()) {
-            let current_coefficient = result
-                .terms
-                .entry(*exponents)
-                .or_insert_with(|| TropicalInt::from(0));
-            *current_coefficient = current_coefficient.clone() + coefficient.clone();
+        TropicalPolynomial {
+            terms: self.terms.merge(rhs.terms),
         }
-
-        result
     }
 }
 
-impl<const N: usize> AddAssign for TropicalPolynomial<N> {
+impl<const N: usize, T: Semiring> AddAssign for TropicalPolynomial<N, T> {
     fn add_assign(&mut self, rhs: Self) {
-        for (exponents, coefficient) in rhs.terms {
-            let current_coefficient = self
-                .terms
-                .entry(exponents)
-                .or_insert_with(|| TropicalInt::from(0));
-            *current_coefficient = current_coefficient.clone() + coefficient;
-        }
+        self.terms = core::mem::take(&mut self.terms).merge(rhs.terms);
     }
 }
 
-impl<const N: usize> Mul for TropicalPolynomial<N> {
+impl<const N: usize, T: Semiring> Mul for TropicalPolynomial<N, T> {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self {
-        let mut result = TropicalPolynomial::new();
+        &self * &rhs
+    }
+}
 
-        for (exponents1, coefficient1) in self.terms.iter() {
-            for (exponents2, coefficient2) in rhs.terms.iter() {
-                let mut new_exponents = [0; N];
-                for i in 0..N {
-                    new_exponents[i] = exponents1[i] + exponents2[i];
-                }
+impl<const N: usize, T: Semiring> Mul for &TropicalPolynomial<N, T> {
+    type Output = TropicalPolynomial<N, T>;
 
-                let coefficient_increment = coefficient1.clone() * coefficient2.clone();
-
-                match coefficient_increment {
-                    // README: it's probably slower to branch but we don't want to have terms in the map with coefficient equal to the additive identity, prefering to omit them.
-                    TropicalInt::AdditiveIdentity => {}
-                    TropicalInt::Integer(_) => {
-                        let _ = result
-                            .terms
-                            .entry(new_exponents)
-                            .and_modify(|current_coefficient| {
-                                *current_coefficient += coefficient_increment
-                            })
-                            .or_insert_with(|| coefficient_increment);
-                    }
-                }
-            }
+    fn mul(self, rhs: Self) -> TropicalPolynomial<N, T> {
+        TropicalPolynomial {
+            terms: self.terms.par_product(&rhs.terms),
         }
-
-        result
     }
 }
 
 // TODO: in-place?
-impl<const N: usize> MulAssign for TropicalPolynomial<N> {
+impl<const N: usize, T: Semiring> MulAssign for TropicalPolynomial<N, T> {
     fn mul_assign(&mut self, rhs: Self) {
         *self = self.clone() * rhs;
     }
@@ -192,7 +611,7 @@ impl<const N: usize> MulAssign for TropicalPolynomial<N> {
 
 // TODO: iterate only once? not sure would be faster and also I don't think we need to
 // optimize this
-impl<const N: usize> PartialEq for TropicalPolynomial<N> {
+impl<const N: usize, T: PartialEq> PartialEq for TropicalPolynomial<N, T> {
     fn eq(&self, other: &Self) -> bool {
         for (exponents1, coefficient1) in self.terms.iter() {
             if let Some(coefficient2) = other.terms.get(exponents1) {
@@ -219,12 +638,11 @@ impl<const N: usize> PartialEq for TropicalPolynomial<N> {
 }
 
 const VARIABLES: &[char] = &['x', 'y', 'z'];
-impl<const N: usize> fmt::Display for TropicalPolynomial<N> {
+impl<const N: usize, T: fmt::Display> fmt::Display for TropicalPolynomial<N, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut terms: Vec<_> = self.terms.iter().collect();
-        terms.sort_by(|a, b| b.0.cmp(a.0)); // Sort in descending order of exponents
-
-        for (i, (exponents, coefficient)) in terms.iter().enumerate() {
+        for (i, (exponents, coefficient)) in
+            self.terms_sorted(MonomialOrder::Lex).iter().enumerate()
+        {
             if i > 0 {
                 write!(f, " + ")?;
             }
@@ -245,7 +663,7 @@ impl<const N: usize> fmt::Display for TropicalPolynomial<N> {
     }
 }
 
-impl<const N: usize> Sum for TropicalPolynomial<N> {
+impl<const N: usize, T: Semiring> Sum for TropicalPolynomial<N, T> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(TropicalPolynomial::additive_identity(), |a, b| a + b)
     }
@@ -253,9 +671,12 @@ impl<const N: usize> Sum for TropicalPolynomial<N> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{tropical_int::TropicalInt, tropical_polynomial::TropicalPolynomial};
+    use crate::{
+        error::TropicalError, linear_program::rational, monomial_order::MonomialOrder,
+        tropical_int::TropicalInt, tropical_polynomial::TropicalPolynomial,
+    };
 
-    use super::Degree;
+    use super::{Degree, LinearFactorization};
 
     #[test]
     fn test_sum_disjoint_polys() {
@@ -362,6 +783,9 @@ mod tests {
                 4,
                 TropicalPolynomial::from(vec![
                     ([4, 0, 0], TropicalInt::from(0)),
+                    ([3, 1, 0], TropicalInt::from(0)),
+                    ([2, 2, 0], TropicalInt::from(0)),
+                    ([1, 3, 0], TropicalInt::from(0)),
                     ([0, 4, 0], TropicalInt::from(0)),
                 ]),
             ),
@@ -373,6 +797,7 @@ mod tests {
                 2,
                 TropicalPolynomial::from(vec![
                     ([8, 0, 6], TropicalInt::from(10)),
+                    ([4, 4, 4], TropicalInt::from(12)),
                     ([0, 8, 2], TropicalInt::from(14)),
                 ]),
             ),
@@ -380,6 +805,35 @@ mod tests {
 
         for (a, power, b) in test_table {
             assert_eq!(a.pow(power), b);
+            assert_eq!(a.pow_functional(power), b.normal_form());
+            assert_eq!(a.pow_pruned(power), b.normal_form());
+        }
+    }
+
+    #[test]
+    fn test_poly_pow_matches_products() {
+        let test_table: Vec<TropicalPolynomial<2>> = vec![
+            TropicalPolynomial::from(vec![
+                ([1, 0], TropicalInt::from(3)),
+                ([0, 1], TropicalInt::from(-2)),
+                ([0, 0], TropicalInt::from(0)),
+            ]),
+            TropicalPolynomial::from(vec![
+                ([2, 1], TropicalInt::from(1)),
+                ([1, 3], TropicalInt::from(4)),
+            ]),
+            TropicalPolynomial::constant(TropicalInt::AdditiveIdentity),
+        ];
+
+        for poly in test_table {
+            let mut product = TropicalPolynomial::multiplicative_identity();
+            for power in 0..=6 {
+                assert_eq!(poly.pow(power), product);
+                assert_eq!(poly.try_pow(power), Ok(product.clone()));
+                assert!(poly.pow_functional(power).functionally_eq(&product));
+                assert_eq!(poly.pow_pruned(power), product.normal_form());
+                product *= poly.clone();
+            }
         }
     }
 
@@ -444,4 +898,304 @@ mod tests {
             assert_eq!(p, q);
         }
     }
+
+    #[test]
+    fn test_sum_negative_coefficients() {
+        let a = TropicalPolynomial::monomial([1, 0], TropicalInt::from(-3));
+        let b = TropicalPolynomial::monomial([0, 1], TropicalInt::from(-5));
+
+        let expected = TropicalPolynomial::from(vec![
+            ([1, 0], TropicalInt::from(-3)),
+            ([0, 1], TropicalInt::from(-5)),
+        ]);
+
+        assert_eq!(a.clone() + b.clone(), expected);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn test_normal_form_1() {
+        let test_table: Vec<(TropicalPolynomial<1>, TropicalPolynomial<1>)> = vec![
+            (
+                // x² + x + 5 = x² + 5
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(0)),
+                    ([1], TropicalInt::from(0)),
+                    ([0], TropicalInt::from(5)),
+                ]),
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(0)),
+                    ([0], TropicalInt::from(5)),
+                ]),
+            ),
+            (
+                // x is a corner of x² + 3x + 5 so it stays
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(0)),
+                    ([1], TropicalInt::from(3)),
+                    ([0], TropicalInt::from(5)),
+                ]),
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(0)),
+                    ([1], TropicalInt::from(3)),
+                    ([0], TropicalInt::from(5)),
+                ]),
+            ),
+            (
+                // 1x is on the segment between x² and 2, it's never strictly the max
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(0)),
+                    ([1], TropicalInt::from(1)),
+                    ([0], TropicalInt::from(2)),
+                ]),
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(0)),
+                    ([0], TropicalInt::from(2)),
+                ]),
+            ),
+            (
+                TropicalPolynomial::additive_identity(),
+                TropicalPolynomial::new(),
+            ),
+        ];
+
+        for (p, q) in test_table {
+            assert_eq!(p.normal_form(), q);
+        }
+    }
+
+    #[test]
+    fn test_normal_form_2() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+
+        // (x + y)² = x² + xy + y² = x² + y²
+        assert_eq!(
+            (x.clone() + y.clone()).pow(2).normal_form(),
+            (x.pow(2) + y.pow(2)).normal_form()
+        );
+        assert_eq!(
+            (x.clone() + y.clone()).pow(2).normal_form(),
+            TropicalPolynomial::from(vec![
+                ([2, 0], TropicalInt::from(0)),
+                ([0, 2], TropicalInt::from(0)),
+            ])
+        );
+
+        // interior points of the newton polytope only survive if they are lifted high enough
+        let triangle = TropicalPolynomial::from(vec![
+            ([0, 0], TropicalInt::from(0)),
+            ([3, 0], TropicalInt::from(0)),
+            ([0, 3], TropicalInt::from(0)),
+        ]);
+        let low = triangle.clone() + TropicalPolynomial::monomial([1, 1], TropicalInt::from(0));
+        let high = triangle.clone() + TropicalPolynomial::monomial([1, 1], TropicalInt::from(1));
+
+        assert_eq!(low.normal_form(), triangle);
+        assert_eq!(high.normal_form(), high);
+    }
+
+    #[test]
+    fn test_functionally_eq() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+
+        let p = (x.clone() + y.clone()).pow(2);
+        let q = x.pow(2) + y.pow(2);
+
+        assert_ne!(p, q);
+        assert!(p.functionally_eq(&q));
+        assert!(!p.functionally_eq(
+            &(q.clone() + TropicalPolynomial::monomial([1, 1], TropicalInt::from(1)))
+        ));
+        assert!(TropicalPolynomial::<2>::additive_identity()
+            .functionally_eq(&TropicalPolynomial::new()));
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+        let constant = |c: i64| TropicalPolynomial::<2>::constant(TropicalInt::from(c));
+
+        let test_table: Vec<[TropicalPolynomial<2>; 4]> = vec![
+            [
+                // (x + 1)(x + 2) / (x + 1) = x + 2
+                (x.clone() + constant(1)) * (x.clone() + constant(2)),
+                x.clone() + constant(1),
+                x.clone() + constant(2),
+                TropicalPolynomial::new(),
+            ],
+            [
+                // (x² + 3) = (x + 1)(x + 1) + 3
+                x.pow(2) + constant(3),
+                x.clone() + constant(1),
+                x.clone() + constant(1),
+                constant(3),
+            ],
+            [
+                // (x + y)(x + 1) / (x + 1) = x + y
+                (x.clone() + y.clone()) * (x.clone() + constant(1)),
+                x.clone() + constant(1),
+                x.clone() + y.clone(),
+                TropicalPolynomial::new(),
+            ],
+            [
+                // x² + y² = x(x) + y²
+                x.pow(2) + y.pow(2),
+                x.clone(),
+                x.clone(),
+                y.pow(2),
+            ],
+        ];
+
+        for [dividend, divisor, quotient, remainder] in test_table {
+            let (q, r) = dividend.div_rem(&divisor).unwrap();
+            assert_eq!(q, quotient);
+            assert_eq!(r, remainder);
+            assert!((q * divisor + r).functionally_eq(&dividend));
+        }
+
+        for divisor in [
+            TropicalPolynomial::new(),
+            TropicalPolynomial::additive_identity(),
+        ] {
+            assert_eq!(
+                (x.clone() + y.clone()).div_rem(&divisor),
+                Err(TropicalError::DivisionByAdditiveIdentity)
+            );
+        }
+    }
+
+    #[test]
+    fn test_exact_quotient() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+        let constant = |c: i64| TropicalPolynomial::<2>::constant(TropicalInt::from(c));
+
+        let p = (x.clone() + y.clone()) * (y.clone() + constant(3));
+        assert!(p.is_divisible_by(&(y.clone() + constant(3))));
+        assert!(p
+            .exact_quotient(&(x.clone() + y.clone()))
+            .unwrap()
+            .functionally_eq(&(y.clone() + constant(3))));
+        assert!(!p.is_divisible_by(&(x.clone() + constant(3))));
+        assert!(!p.is_divisible_by(&TropicalPolynomial::additive_identity()));
+    }
+
+    #[test]
+    fn test_factor() {
+        let constant = |c: i64| TropicalPolynomial::<1>::constant(TropicalInt::from(c));
+        let x = TropicalPolynomial::<1>::variable(0);
+
+        let test_table: Vec<(TropicalPolynomial<1>, LinearFactorization)> = vec![
+            (
+                // x(x + 1)(x + 3) = x³ + 3x² + 4x
+                x.clone() * (x.clone() + constant(1)) * (x.clone() + constant(3)),
+                LinearFactorization {
+                    leading_coefficient: TropicalInt::zero(),
+                    lowest_degree: 1,
+                    roots: vec![(rational(1), 1), (rational(3), 1)],
+                },
+            ),
+            (
+                // 2x² + 5 = 2(x + 3/2)²
+                TropicalPolynomial::from(vec![
+                    ([2], TropicalInt::from(2)),
+                    ([1], TropicalInt::from(1)),
+                    ([0], TropicalInt::from(5)),
+                ]),
+                LinearFactorization {
+                    leading_coefficient: TropicalInt::from(2),
+                    lowest_degree: 0,
+                    roots: vec![(rational(3) / rational(2), 2)],
+                },
+            ),
+            (
+                TropicalPolynomial::monomial([4], TropicalInt::from(-1)),
+                LinearFactorization {
+                    leading_coefficient: TropicalInt::from(-1),
+                    lowest_degree: 4,
+                    roots: vec![],
+                },
+            ),
+        ];
+
+        for (poly, factorization) in test_table {
+            assert_eq!(poly.factor_linear(), factorization);
+            assert!(factorization.expand().functionally_eq(&poly));
+        }
+    }
+
+    #[test]
+    fn test_try_mul() {
+        let poly: TropicalPolynomial<2> = TropicalPolynomial::from(vec![
+            ([1, 0], TropicalInt::from(3)),
+            ([0, 2], TropicalInt::from(-1)),
+        ]);
+        let test_table: Vec<(
+            TropicalPolynomial<2>,
+            Result<TropicalPolynomial<2>, TropicalError>,
+        )> = vec![
+            (poly.clone(), Ok(poly.clone() * poly.clone())),
+            (
+                TropicalPolynomial::constant(TropicalInt::from(i64::MAX)),
+                Err(TropicalError::Overflow),
+            ),
+            (
+                TropicalPolynomial::monomial([Degree::MAX, 0], TropicalInt::from(0)),
+                Err(TropicalError::Overflow),
+            ),
+        ];
+
+        for (rhs, product) in test_table {
+            assert_eq!(poly.try_mul(&rhs), product);
+        }
+
+        assert_eq!(poly.try_pow(3), Ok(poly.pow(3)));
+        assert_eq!(
+            TropicalPolynomial::<2>::constant(TropicalInt::from(i64::MAX / 2)).try_pow(3),
+            Err(TropicalError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_leading_term() {
+        let poly: TropicalPolynomial<3> = TropicalPolynomial::from(vec![
+            ([1, 5, 2], TropicalInt::from(1)),
+            ([4, 1, 3], TropicalInt::from(2)),
+            ([0, 0, 8], TropicalInt::from(3)),
+            ([0, 1, 0], TropicalInt::from(4)),
+        ]);
+        // order, leading degree, trailing degree
+        let test_table: Vec<(MonomialOrder, [Degree; 3], [Degree; 3])> = vec![
+            (MonomialOrder::Lex, [4, 1, 3], [0, 0, 8]),
+            (MonomialOrder::GradedLex, [4, 1, 3], [0, 1, 0]),
+            (MonomialOrder::GradedReverseLex, [1, 5, 2], [0, 1, 0]),
+        ];
+
+        for (order, leading, trailing) in test_table {
+            let sorted = poly.terms_sorted(order);
+            assert_eq!(sorted.len(), 4);
+            assert_eq!(*sorted[0].0, leading);
+            assert_eq!(*sorted[3].0, trailing);
+            assert_eq!(poly.leading_monomial(order), Some(leading));
+            assert_eq!(
+                poly.leading_term(order),
+                Some((&leading, poly.get_term(&leading).unwrap()))
+            );
+            assert_eq!(
+                poly.trailing_term(order),
+                Some((&trailing, poly.get_term(&trailing).unwrap()))
+            );
+        }
+
+        assert_eq!(
+            TropicalPolynomial::<3>::new().leading_term(MonomialOrder::Lex),
+            None
+        );
+    }
 }
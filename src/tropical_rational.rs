@@ -1,87 +1,263 @@
 use core::fmt;
 use core::ops::{Add, Div, DivAssign, Mul, MulAssign};
 
+use crate::error::TropicalError;
+use crate::semiring::{Semifield, Semiring, TropicalScalar};
 use crate::tropical_int::TropicalInt;
 use crate::tropical_polynomial::{Degree, TropicalPolynomial};
 
 #[derive(PartialEq, Clone, Debug)]
-pub struct TropicalRational<const N: usize> {
-    pub(crate) numerator: TropicalPolynomial<N>,
-    pub(crate) denominator: TropicalPolynomial<N>,
+pub struct TropicalRational<const N: usize, T = TropicalInt> {
+    pub(crate) numerator: TropicalPolynomial<N, T>,
+    pub(crate) denominator: TropicalPolynomial<N, T>,
 }
 
-impl<const N: usize> TropicalRational<N> {
-    pub fn new(numerator: TropicalPolynomial<N>, denominator: TropicalPolynomial<N>) -> Self {
+impl<const N: usize, T: Semiring> TropicalRational<N, T> {
+    pub fn new(numerator: TropicalPolynomial<N, T>, denominator: TropicalPolynomial<N, T>) -> Self {
         TropicalRational {
             numerator,
             denominator,
         }
     }
 
-    pub fn polynomial(numerator: TropicalPolynomial<N>) -> Self {
-        Self::new(
-            numerator,
-            TropicalPolynomial::from(vec![([0; N], TropicalInt::from(0))]),
-        )
+    pub fn polynomial(numerator: TropicalPolynomial<N, T>) -> Self {
+        Self::new(numerator, TropicalPolynomial::multiplicative_identity())
     }
 
     pub fn pow(&self, exponent: Degree) -> Self {
         TropicalRational::new(self.numerator.pow(exponent), self.denominator.pow(exponent))
     }
+}
 
-    // TODO: (hard)
+impl<const N: usize, T: Semifield> TropicalRational<N, T> {
+    /// `TropicalError::DivisionByAdditiveIdentity` when the denominator is -∞ at the point,
+    /// over `CompletedTropicalInt` that is +∞ instead.
+    pub fn evaluate(&self, variables: [T; N]) -> Result<T, TropicalError> {
+        self.numerator
+            .evaluate(variables.clone())
+            .checked_div(&self.denominator.evaluate(variables))
+    }
+}
+
+impl<const N: usize, T: TropicalScalar> TropicalRational<N, T> {
+    /*
+        README: this follows the spirit of "Minimal Representations of Tropical Rational
+        Functions" without being the full algorithm. we drop dominated terms (normal forms),
+        cancel the whole denominator (or numerator) when it divides the other side, and then
+        strip the common monomial content, so negative degrees end up as a monomial
+        denominator. the coefficients are shifted so the denominator's largest one is 0.
+        in one variable we factor both sides and cancel the common roots instead, which
+        gives the minimal representation. in more variables common factors that are proper
+        factors of both sides are not found yet, so `(x + y)(x + z) / (x + y)(y + z)` stays
+        as it is.
+        `+`, `*` and `/` only do the cheap part (see `reduce`), call this one explicitly when
+        the representation matters.
+    */
     pub fn simplify(&self) -> Self {
-        self.clone()
+        let numerator = self.numerator.normal_form();
+        let denominator = self.denominator.normal_form();
+
+        if numerator.terms.is_empty() {
+            return Self::new(numerator, TropicalPolynomial::multiplicative_identity());
+        }
+        if denominator.terms.is_empty() {
+            return Self::new(numerator, denominator);
+        }
+
+        let (numerator, denominator) = if N == 1 {
+            cancel_common_roots(&numerator, &denominator)
+        } else if let Some(quotient) = numerator.exact_quotient(&denominator) {
+            (quotient, TropicalPolynomial::multiplicative_identity())
+        } else if let Some(quotient) = denominator.exact_quotient(&numerator) {
+            (TropicalPolynomial::multiplicative_identity(), quotient)
+        } else {
+            (numerator, denominator)
+        };
+
+        let content = monomial_content(&numerator, &denominator);
+        let scale = denominator
+            .terms
+            .values()
+            .cloned()
+            .fold(T::additive_identity(), |acc, c| acc + c);
+
+        let strip = |poly: TropicalPolynomial<N, T>| {
+            TropicalPolynomial::from(
+                poly.terms
+                    .into_iter()
+                    .map(|(multi_degree, coefficient)| {
+                        (
+                            core::array::from_fn(|i| multi_degree[i] - content[i]),
+                            coefficient / scale.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        Self::new(strip(numerator), strip(denominator))
+    }
+
+    /*
+        README: normal forms and the common monomial content, no factoring. this keeps the
+        sides of `+`, `*` and `/` from growing with dominated terms and powers of the
+        variables, it still can't cancel a common factor like `x + y`.
+    */
+    fn reduce(numerator: TropicalPolynomial<N, T>, denominator: TropicalPolynomial<N, T>) -> Self {
+        let numerator = numerator.normal_form();
+        let denominator = denominator.normal_form();
+
+        if numerator.terms.is_empty() {
+            return Self::new(numerator, TropicalPolynomial::multiplicative_identity());
+        }
+        if denominator.terms.is_empty() {
+            return Self::new(numerator, denominator);
+        }
+
+        let content = monomial_content(&numerator, &denominator);
+        let strip = |poly: TropicalPolynomial<N, T>| {
+            TropicalPolynomial::from(
+                poly.terms
+                    .into_iter()
+                    .map(|(multi_degree, coefficient)| {
+                        (
+                            core::array::from_fn(|i| multi_degree[i] - content[i]),
+                            coefficient,
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        Self::new(strip(numerator), strip(denominator))
+    }
+
+    /// Compares both rational functions as functions by cross-multiplying, since
+    /// `p / q = r / s` iff `ps = rq`.
+    pub fn functionally_eq(&self, other: &Self) -> bool {
+        (self.numerator.clone() * other.denominator.clone())
+            .functionally_eq(&(other.numerator.clone() * self.denominator.clone()))
+    }
+}
+
+// README: the largest monomial dividing every term of both sides, neither can be empty
+fn monomial_content<const N: usize, T>(
+    numerator: &TropicalPolynomial<N, T>,
+    denominator: &TropicalPolynomial<N, T>,
+) -> [Degree; N] {
+    core::array::from_fn(|i| {
+        numerator
+            .terms
+            .keys()
+            .chain(denominator.terms.keys())
+            .map(|multi_degree| multi_degree[i])
+            .min()
+            .unwrap()
+    })
+}
+
+// README: only meant for N = 1, it's generic so `simplify` doesn't need specialization
+fn cancel_common_roots<const N: usize, T: TropicalScalar>(
+    numerator: &TropicalPolynomial<N, T>,
+    denominator: &TropicalPolynomial<N, T>,
+) -> (TropicalPolynomial<N, T>, TropicalPolynomial<N, T>) {
+    debug_assert_eq!(N, 1);
+
+    let univariate = |poly: &TropicalPolynomial<N, T>| {
+        TropicalPolynomial::<1, T>::from(
+            poly.terms
+                .iter()
+                .map(|(multi_degree, coefficient)| ([multi_degree[0]], coefficient.clone()))
+                .collect::<Vec<_>>(),
+        )
+    };
+    let multivariate = |poly: TropicalPolynomial<1, T>| {
+        TropicalPolynomial::<N, T>::from(
+            poly.terms
+                .into_iter()
+                .map(|([degree], coefficient)| (core::array::from_fn(|_| degree), coefficient))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let mut numerator = univariate(numerator).factor_linear();
+    let mut denominator = univariate(denominator).factor_linear();
+
+    for (root, multiplicity) in numerator.roots.iter_mut() {
+        if let Some((_, other_multiplicity)) = denominator
+            .roots
+            .iter_mut()
+            .find(|(other_root, _)| other_root == root)
+        {
+            let common = (*multiplicity).min(*other_multiplicity);
+            *multiplicity -= common;
+            *other_multiplicity -= common;
+        }
     }
+    numerator
+        .roots
+        .retain(|(_, multiplicity)| *multiplicity > 0);
+    denominator
+        .roots
+        .retain(|(_, multiplicity)| *multiplicity > 0);
+
+    (
+        multivariate(numerator.expand()),
+        multivariate(denominator.expand()),
+    )
 }
 
-impl<const N: usize> Add for TropicalRational<N> {
+impl<const N: usize, T: TropicalScalar> Add for TropicalRational<N, T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
+        if self.denominator == rhs.denominator {
+            return Self::reduce(self.numerator + rhs.numerator, self.denominator);
+        }
+
         let new_numerator = (self.numerator.clone() * rhs.denominator.clone())
             + (rhs.numerator.clone() * self.denominator.clone());
         let new_denominator = self.denominator * rhs.denominator;
 
-        TropicalRational::new(new_numerator, new_denominator).simplify()
+        Self::reduce(new_numerator, new_denominator)
     }
 }
 
-impl<const N: usize> Mul for TropicalRational<N> {
+impl<const N: usize, T: TropicalScalar> Mul for TropicalRational<N, T> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
         let new_numerator = self.numerator * other.numerator;
         let new_denominator = self.denominator * other.denominator;
 
-        TropicalRational::new(new_numerator, new_denominator).simplify()
+        Self::reduce(new_numerator, new_denominator)
     }
 }
 
-impl<const N: usize> MulAssign for TropicalRational<N> {
+impl<const N: usize, T: TropicalScalar> MulAssign for TropicalRational<N, T> {
     fn mul_assign(&mut self, other: Self) {
         *self = self.clone() * other;
     }
 }
 
-impl<const N: usize> Div for TropicalRational<N> {
+impl<const N: usize, T: TropicalScalar> Div for TropicalRational<N, T> {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self {
-        TropicalRational::new(
+        Self::reduce(
             self.numerator * rhs.denominator,
             self.denominator * rhs.numerator,
         )
     }
 }
 
-impl<const N: usize> DivAssign for TropicalRational<N> {
+impl<const N: usize, T: TropicalScalar> DivAssign for TropicalRational<N, T> {
     fn div_assign(&mut self, rhs: Self) {
         *self = self.clone() / rhs;
     }
 }
 
-impl<const N: usize> fmt::Display for TropicalRational<N> {
+impl<const N: usize, T: fmt::Display> fmt::Display for TropicalRational<N, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({}) / ({})", self.numerator, self.denominator)
     }
@@ -90,7 +266,10 @@ impl<const N: usize> fmt::Display for TropicalRational<N> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        tropical_int::TropicalInt, tropical_polynomial::TropicalPolynomial,
+        error::TropicalError,
+        linear_program::rational,
+        tropical_int::TropicalInt,
+        tropical_polynomial::{Degree, TropicalPolynomial},
         tropical_rational::TropicalRational,
     };
 
@@ -115,4 +294,179 @@ mod tests {
         println!("r1 * r2 = {}", r1.clone() * r2.clone());
         println!("r1 / r2 = {}", r1 / r2);
     }
+
+    #[test]
+    fn test_arithmetic_reduces() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+        let zero = TropicalPolynomial::<2>::multiplicative_identity();
+
+        // x / y + x / y = x / y
+        let r = TropicalRational::new(x.clone(), y.clone());
+        assert_eq!(r.clone() + r.clone(), r);
+
+        // (x / y)(y / x) = 0 / 0
+        assert_eq!(
+            r.clone() * TropicalRational::new(y.clone(), x.clone()),
+            TropicalRational::new(zero.clone(), zero.clone())
+        );
+
+        // adding 0 / (x + 0) to itself doesn't grow the denominator
+        let r = TropicalRational::new(zero.clone(), x.clone() + zero.clone());
+        assert_eq!((0..8).fold(r.clone(), |acc, _| acc + r.clone()), r);
+
+        // x(x + y) / x²y = (x + y) / xy, the common x + y of both sides isn't cancelled though
+        let r = TropicalRational::new(x.clone() + y.clone(), x.clone())
+            * TropicalRational::new(x.clone(), x.clone() * y.clone());
+        assert_eq!(
+            r,
+            TropicalRational::new(x.clone() + y.clone(), x.clone() * y.clone())
+        );
+        let r = r * TropicalRational::new(x.clone(), x.clone() + y.clone());
+        assert!(r.functionally_eq(&TropicalRational::new(zero, y)));
+        assert_eq!(r.numerator.terms.len(), 2);
+    }
+
+    #[test]
+    fn test_functionally_eq() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+
+        // (x + y)² / x = (x² + y²) / x
+        assert!(
+            TropicalRational::new((x.clone() + y.clone()).pow(2), x.clone())
+                .functionally_eq(&TropicalRational::new(x.pow(2) + y.pow(2), x.clone()))
+        );
+
+        // xy / y = x / 0
+        assert!(TropicalRational::new(x.clone() * y.clone(), y.clone())
+            .functionally_eq(&TropicalRational::polynomial(x.clone())));
+
+        // (x² + y²) / (x + y) = x + y
+        assert!(
+            TropicalRational::new(x.pow(2) + y.pow(2), x.clone() + y.clone())
+                .functionally_eq(&TropicalRational::polynomial(x.clone() + y.clone()))
+        );
+
+        assert!(!TropicalRational::new(x.clone(), y.clone())
+            .functionally_eq(&TropicalRational::new(y, x)));
+    }
+
+    #[test]
+    fn test_simplify() {
+        let x = TropicalPolynomial::<3>::variable(0);
+        let y = TropicalPolynomial::<3>::variable(1);
+        let z = TropicalPolynomial::<3>::variable(2);
+        let monomial = |multi_degree: [Degree; 3], coefficient: i64| {
+            TropicalPolynomial::monomial(multi_degree, TropicalInt::from(coefficient))
+        };
+
+        let test_table: Vec<(TropicalRational<3>, TropicalRational<3>)> = vec![
+            (
+                // x(y + z) / (y + z) = x
+                TropicalRational::new(x.clone() * (y.clone() + z.clone()), y.clone() + z.clone()),
+                TropicalRational::polynomial(x.clone()),
+            ),
+            (
+                // (x² + y²) / (x + y) = x + y
+                TropicalRational::new(x.pow(2) + y.pow(2), x.clone() + y.clone()),
+                TropicalRational::polynomial(x.clone() + y.clone()),
+            ),
+            (
+                // (x + y) / (x² + xy + y²) = 0 / (x + y)
+                TropicalRational::new(
+                    x.clone() + y.clone(),
+                    x.pow(2) + x.clone() * y.clone() + y.pow(2),
+                ),
+                TropicalRational::new(
+                    TropicalPolynomial::multiplicative_identity(),
+                    x.clone() + y.clone(),
+                ),
+            ),
+            (
+                // 3xy / 1y² = 2x / y
+                TropicalRational::new(monomial([1, 1, 0], 3), monomial([0, 2, 0], 1)),
+                TropicalRational::new(monomial([1, 0, 0], 2), y.clone()),
+            ),
+            (
+                // 1xz / 1(xy + xz) = 0z / (y + z)
+                TropicalRational::new(
+                    monomial([1, 0, 1], 1),
+                    monomial([1, 1, 0], 1) + monomial([1, 0, 1], 1),
+                ),
+                TropicalRational::new(z.clone(), y.clone() + z.clone()),
+            ),
+            (
+                // nothing to cancel in (x + y) / (x + z)
+                TropicalRational::new(x.clone() + y.clone(), x.clone() + z.clone()),
+                TropicalRational::new(x.clone() + y.clone(), x.clone() + z.clone()),
+            ),
+        ];
+
+        for (r, s) in test_table {
+            let simplified = r.simplify();
+            assert!(simplified.functionally_eq(&r));
+            assert_eq!(simplified, s);
+        }
+    }
+
+    #[test]
+    fn test_simplify_univariate() {
+        let x = TropicalPolynomial::<1>::variable(0);
+        let constant = |c: i64| TropicalPolynomial::<1>::constant(TropicalInt::from(c));
+
+        // (x + 1)(x + 3) / (x + 1)(x + 5)² = (x + 3) / (x + 5)²
+        let r = TropicalRational::new(
+            (x.clone() + constant(1)) * (x.clone() + constant(3)),
+            (x.clone() + constant(1)) * (x.clone() + constant(5)).pow(2),
+        );
+        let s = TropicalRational::new(x.clone() + constant(3), (x.clone() + constant(5)).pow(2));
+
+        assert!(r.simplify().functionally_eq(&r));
+        assert_eq!(r.simplify(), s.simplify());
+        assert_eq!(
+            r.simplify().numerator.factor_linear().roots,
+            vec![(rational(3), 1)]
+        );
+
+        // 2x² + 5 / (x² + 3) = 2
+        let r = TropicalRational::new(
+            TropicalPolynomial::from(vec![
+                ([2], TropicalInt::from(2)),
+                ([0], TropicalInt::from(5)),
+            ]),
+            x.pow(2) + constant(3),
+        );
+        assert_eq!(r.simplify(), TropicalRational::polynomial(constant(2)));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let x = TropicalPolynomial::<2>::variable(0);
+        let y = TropicalPolynomial::<2>::variable(1);
+        let constant = |c: i64| TropicalPolynomial::<2>::constant(TropicalInt::from(c));
+
+        // (x² + 1y) / (x + y + 3)
+        let r = TropicalRational::new(
+            x.pow(2) + constant(1) * y.clone(),
+            x.clone() + y.clone() + constant(3),
+        );
+
+        let test_table: Vec<([i64; 2], i64)> =
+            vec![([0, 0], 1 - 3), ([5, 1], 10 - 5), ([-2, 4], 5 - 4)];
+
+        for (point, value) in test_table {
+            assert_eq!(
+                r.evaluate(point.map(TropicalInt::from)),
+                Ok(TropicalInt::from(value))
+            );
+        }
+
+        // the denominator x is -∞ at (-∞, 0)
+        assert_eq!(
+            TropicalRational::new(y.clone(), x.clone())
+                .evaluate([TropicalInt::AdditiveIdentity, TropicalInt::zero()]),
+            Err(TropicalError::DivisionByAdditiveIdentity)
+        );
+    }
 }